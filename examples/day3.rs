@@ -2,7 +2,7 @@ extern crate advent;
 
 use advent::day3::*;
 
-const TRIANGLES: &'static str = "  775  785  361
+const TRIANGLES: &str = "  775  785  361
   622  375  125
   297  839  375
   245   38  891
@@ -1918,6 +1918,6 @@ const TRIANGLES: &'static str = "  775  785  361
   665  375  287";
 
 pub fn main() {
-    println!("hz sides: {}", count_by_line(TRIANGLES));
-    println!("vz sides: {}", count_by_column(TRIANGLES));
+    println!("hz sides: {}", count_by_line(TRIANGLES).unwrap());
+    println!("vz sides: {}", count_by_column(TRIANGLES).unwrap());
 }
@@ -2,7 +2,9 @@ extern crate advent;
 
 use advent::day4::*;
 
-const ROOMS: &'static str = "vxupkizork-sgmtkzoi-pkrrehkgt-zxgototm-644[kotgr]
+use std::convert::TryFrom;
+
+const ROOMS: &str = "vxupkizork-sgmtkzoi-pkrrehkgt-zxgototm-644[kotgr]
 mbiyqoxsm-pvygob-nocsqx-900[obmqs]
 veqtekmrk-ikk-hitpscqirx-334[nrtws]
 gvcskirmg-fyrrc-irkmriivmrk-932[rikmc]
@@ -940,14 +942,14 @@ shoewudys-tou-ixyffydw-478[uszty]";
 
 pub fn main() {
     let valid_rooms = ROOMS.lines()
-        .map(|line| Room::from(line))
+        .map(|line| Room::try_from(line).unwrap())
         .filter(|room| room.is_valid())
         .collect::<Vec<_>>();
     let sector_sum = valid_rooms.iter()
         .map(|room| room.sector)
         .sum::<u32>();
     println!("Sector sum: {}", sector_sum);
-    for room in valid_rooms.iter().filter(|room| room.decrypt_name().contains("north")) {
-        println!("{:?} ({})", room, room.decrypt_name());
+    for found in Query::substring("north").search(valid_rooms) {
+        println!("{:?} ({})", found.room, found.room.decrypt_name());
     }
 }
@@ -2,13 +2,19 @@ extern crate advent;
 
 use advent::day5::*;
 
-use std::thread;
+use std::env;
 
-const INPUT: &'static str = "ojvtpuvg";
+const INPUT: &str = "ojvtpuvg";
 
 pub fn main() {
-    let basic = thread::spawn(|| get_password(INPUT));
-    let indexed = thread::spawn(|| get_indexed_password(INPUT));
-    println!("{} (basic): {}", INPUT, basic.join().unwrap());
-    println!("{} (indexed): {}", INPUT, indexed.join().unwrap());
+    let dir = env::temp_dir().join("advent-2016");
+    let stream = ZeroHashStream::new(INPUT);
+    let zeros = stream.zeros();
+    let mut cache = HitCache::load(stream, &dir).unwrap_or_else(|e| {
+        eprintln!("ignoring unreadable cache: {}", e);
+        HitCache::new(ZeroHashStream::new(INPUT))
+    });
+    println!("{} (basic): {}", INPUT, password_from(cache.hits(), zeros));
+    println!("{} (indexed): {}", INPUT, indexed_password_from(cache.hits(), zeros));
+    cache.save(&dir).unwrap();
 }
@@ -2,7 +2,7 @@ extern crate advent;
 
 use advent::day6::*;
 
-const JAMMED: &'static str = "blrqqadw
+const JAMMED: &str = "blrqqadw
 hxwteava
 xtzzneor
 ufydaiyx
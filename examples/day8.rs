@@ -2,7 +2,7 @@ extern crate advent;
 
 use advent::day8::*;
 
-const COMMANDS: &'static str = "rect 1x1
+const COMMANDS: &str = "rect 1x1
 rotate row y=0 by 5
 rect 1x1
 rotate row y=0 by 5
@@ -176,7 +176,7 @@ rotate column x=0 by 1";
 pub fn main() {
     let display = COMMANDS.lines()
         .fold(Display::default(), |mut display, cmd| {
-            display.command_str(cmd);
+            display.command_str(cmd).unwrap();
             display
         });
     println!("Display: ({} active pixels)\n{}", display.active_pixels(), display);
@@ -1,10 +1,167 @@
+#[cfg(feature = "bigint")]
+extern crate num_bigint;
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::slice;
+use std::str::FromStr;
+
+#[cfg(feature = "bigint")]
+use self::num_bigint::BigInt;
+
+use super::{ParseError, Solution};
+use error::{column_at, parse_number};
+
+/// A number which can be used as a coordinate.
+///
+/// Implemented for `i32`, `i64` and `i128`, and for `BigInt` when
+/// the `bigint` feature is enabled. Arithmetic is checked, returning
+/// `None` rather than overflowing.
+pub trait Coord: Clone + Ord + Hash + fmt::Debug + fmt::Display + FromStr {
+    /// The additive identity.
+    fn zero() -> Self;
+
+    /// The multiplicative identity.
+    fn one() -> Self;
+
+    /// Add two numbers, if the result fits.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// Subtract two numbers, if the result fits.
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+
+    /// Negate a number, if the result fits.
+    fn checked_neg(&self) -> Option<Self>;
+
+    /// Halve a number, if it is even.
+    fn exact_half(&self) -> Option<Self>;
+
+    /// Convert a number to an `i128`, if it fits.
+    fn to_i128(&self) -> Option<i128>;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            fn zero() -> Self {
+                0
+            }
+
+            fn one() -> Self {
+                1
+            }
+
+            fn checked_add(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_add(*self, *other)
+            }
+
+            fn checked_sub(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_sub(*self, *other)
+            }
+
+            fn checked_neg(&self) -> Option<Self> {
+                <$t>::checked_neg(*self)
+            }
+
+            fn exact_half(&self) -> Option<Self> {
+                if *self % 2 == 0 { Some(*self / 2) } else { None }
+            }
+
+            fn to_i128(&self) -> Option<i128> {
+                i128::try_from(*self).ok()
+            }
+        }
+    )*}
+}
+
+impl_coord!(i32, i64, i128);
+
+#[cfg(feature = "bigint")]
+impl Coord for BigInt {
+    fn zero() -> Self {
+        BigInt::from(0)
+    }
+
+    fn one() -> Self {
+        BigInt::from(1)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn checked_neg(&self) -> Option<Self> {
+        Some(-self)
+    }
+
+    fn exact_half(&self) -> Option<Self> {
+        let two = BigInt::from(2);
+        if self % &two == Self::zero() { Some(self / two) } else { None }
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        i128::try_from(self).ok()
+    }
+}
+
+/// A coordinate, or a distance, which doesn't fit in its type.
+#[derive(Debug,PartialEq,Clone,Copy)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "coordinate overflow")
+    }
+}
+
+impl Error for Overflow {}
+
+/// Multiply by a single step, which is -1, 0 or 1.
+fn scale<T: Coord>(x: &T, step: i8) -> Result<T, Overflow> {
+    match step {
+        0 => Ok(T::zero()),
+        s if s > 0 => Ok(x.clone()),
+        _ => x.checked_neg().ok_or(Overflow),
+    }
+}
+
+/// Divide by a small divisor, which is -2, -1, 1 or 2. Returns
+/// `None` if the division isn't exact.
+fn divide<T: Coord>(x: &T, divisor: i8) -> Result<Option<T>, Overflow> {
+    let x = scale(x, divisor.signum())?;
+    Ok(if divisor.abs() == 1 { Some(x) } else { x.exact_half() })
+}
+
+/// Get the absolute value of a number.
+fn abs<T: Coord>(x: &T) -> Result<T, Overflow> {
+    if *x < T::zero() {
+        x.checked_neg().ok_or(Overflow)
+    } else {
+        Ok(x.clone())
+    }
+}
+
 /// A Direciton of Travel
-#[derive(Debug,PartialEq)]
+///
+/// Variants are listed clockwise from north. The diagonal headings
+/// are only reachable with the `Extended` grammar.
+#[derive(Debug,PartialEq,Clone,Copy)]
 pub enum Direction {
     North,
+    NorthEast,
     East,
+    SouthEast,
     South,
+    SouthWest,
     West,
+    NorthWest,
 }
 
 /// A turn
@@ -12,89 +169,564 @@ pub enum Direction {
 pub enum Turn {
     Left,
     Right,
+    /// Turn around to face the opposite direction.
+    Reverse,
+    /// Carry on in the current direction.
+    Forward,
+    /// Face a given compass heading.
+    Face(Direction),
+}
+
+/// The grammar used when parsing moves.
+#[derive(Debug,PartialEq,Clone,Copy)]
+pub enum Grammar {
+    /// Only `L` and `R` turns, as on the recruiting document.
+    Standard,
+    /// Also allows `B` to reverse, `F` to carry straight on and
+    /// compass headings, such as `N` or `SW`, to face that way.
+    Extended,
+}
+
+/// A way of measuring distance from the origin.
+#[derive(Debug,PartialEq,Clone,Copy)]
+pub enum Metric {
+    /// Blocks travelled along the grid.
+    Manhattan,
+    /// Blocks travelled when diagonal steps are allowed.
+    Chebyshev,
 }
 
 /// A position in 2d space
-#[derive(Debug,PartialEq,Clone)]
-pub struct Position(i32, i32);
+#[derive(Debug,PartialEq,Eq,Hash,Clone)]
+pub struct Position<T = i32>(T, T);
 
 /// A 'move' in the path
 #[derive(Debug,PartialEq)]
-pub struct Move(Turn, i32);
+pub struct Move<T = i32>(Turn, T);
+
+/// A straight section of a route. Covers each position from `start`
+/// to `len` blocks away in `direction`.
+#[derive(Debug)]
+struct Segment<T> {
+    start: Position<T>,
+    direction: Direction,
+    len: T,
+}
 
 impl Direction {
+    /// All directions, clockwise from north.
+    const CLOCKWISE: [Direction; 8] = [Direction::North,
+                                       Direction::NorthEast,
+                                       Direction::East,
+                                       Direction::SouthEast,
+                                       Direction::South,
+                                       Direction::SouthWest,
+                                       Direction::West,
+                                       Direction::NorthWest];
+
     /// Return the direciton after turning.
     pub fn turn(self, t: Turn) -> Self {
+        let eighths = match t {
+            Turn::Forward => 0,
+            Turn::Right => 2,
+            Turn::Reverse => 4,
+            Turn::Left => 6,
+            Turn::Face(heading) => return heading,
+        };
+        Self::CLOCKWISE[(self as usize + eighths) % 8]
+    }
+
+    /// Get the change in northings and eastings for a single step
+    /// in this direction.
+    fn step(&self) -> (i8, i8) {
         use self::Direction::*;
-        match t {
-            Turn::Left => {
-                match self {
-                    North => West,
-                    West => South,
-                    South => East,
-                    East => North,
-                }
-            }
-            Turn::Right => {
-                match self {
-                    North => East,
-                    West => North,
-                    South => West,
-                    East => South,
-                }
-            }
+        match *self {
+            North => (1, 0),
+            NorthEast => (1, 1),
+            East => (0, 1),
+            SouthEast => (-1, 1),
+            South => (-1, 0),
+            SouthWest => (-1, -1),
+            West => (0, -1),
+            NorthWest => (1, -1),
+        }
+    }
+}
+
+impl Grammar {
+    /// Get the distance metric which suits routes in this grammar.
+    /// Once diagonal moves are allowed distances are measured with
+    /// the `Chebyshev` metric.
+    pub fn metric(&self) -> Metric {
+        match *self {
+            Grammar::Standard => Metric::Manhattan,
+            Grammar::Extended => Metric::Chebyshev,
         }
     }
 }
 
-impl Position {
+impl<T: Coord> Default for Position<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Coord> Position<T> {
     pub fn new() -> Self {
-        Position(0, 0)
+        Position(T::zero(), T::zero())
     }
 
     /// Move in a given direction by a distance.
-    pub fn move_by(&self, direction: &Direction, distance: i32) -> Self {
-        let &Position(northings, eastings) = self;
-        match *direction {
-            Direction::North => Position(northings + distance, eastings),
-            Direction::South => Position(northings - distance, eastings),
-            Direction::West => Position(northings, eastings - distance),
-            Direction::East => Position(northings, eastings + distance),
-        }
+    pub fn move_by(&self, direction: &Direction, distance: T) -> Result<Self, Overflow> {
+        let (north, east) = direction.step();
+        let northings = self.0.checked_add(&scale(&distance, north)?).ok_or(Overflow)?;
+        let eastings = self.1.checked_add(&scale(&distance, east)?).ok_or(Overflow)?;
+        Ok(Position(northings, eastings))
     }
 
     /// Get the travel distance
-    pub fn travel_dist(&self) -> i32 {
-        let &Position(northings, eastings) = self;
-        northings.abs() + eastings.abs()
+    pub fn travel_dist(&self) -> Result<T, Overflow> {
+        self.distance(Metric::Manhattan)
+    }
+
+    /// Get the distance from the origin using a given metric.
+    pub fn distance(&self, metric: Metric) -> Result<T, Overflow> {
+        let (northings, eastings) = (abs(&self.0)?, abs(&self.1)?);
+        match metric {
+            Metric::Manhattan => northings.checked_add(&eastings).ok_or(Overflow),
+            Metric::Chebyshev => Ok(northings.max(eastings)),
+        }
     }
 }
 
-impl Move {
+impl<T: Coord> Move<T> {
     /// get the turn
     pub fn turn(&self) -> Turn {
-        let &Move(ref turn, _) = self;
-        turn.clone()
+        self.0.clone()
     }
 
     /// get the distance
-    pub fn dist(&self) -> i32 {
-        let &Move(_, ref dist) = self;
-        dist.clone()
+    pub fn dist(&self) -> T {
+        self.1.clone()
+    }
+
+    /// Parse a move from a string using the given grammar.
+    pub fn parse_with(s: &str, grammar: Grammar) -> Result<Self, ParseError> {
+        use self::Direction::*;
+        let (turn, len) = match grammar {
+            Grammar::Standard => {
+                match s.chars().next() {
+                    Some('L') => (Turn::Left, 1),
+                    Some('R') => (Turn::Right, 1),
+                    Some(c) => return Err(ParseError::unexpected_char(1, "'L' or 'R'", c)),
+                    None => return Err(ParseError::unexpected_end(1, "a move")),
+                }
+            }
+            Grammar::Extended => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some('L'), _) => (Turn::Left, 1),
+                    (Some('R'), _) => (Turn::Right, 1),
+                    (Some('B'), _) => (Turn::Reverse, 1),
+                    (Some('F'), _) => (Turn::Forward, 1),
+                    (Some('N'), Some('E')) => (Turn::Face(NorthEast), 2),
+                    (Some('N'), Some('W')) => (Turn::Face(NorthWest), 2),
+                    (Some('S'), Some('E')) => (Turn::Face(SouthEast), 2),
+                    (Some('S'), Some('W')) => (Turn::Face(SouthWest), 2),
+                    (Some('N'), _) => (Turn::Face(North), 1),
+                    (Some('E'), _) => (Turn::Face(East), 1),
+                    (Some('S'), _) => (Turn::Face(South), 1),
+                    (Some('W'), _) => (Turn::Face(West), 1),
+                    (Some(c), _) => {
+                        return Err(ParseError::unexpected_char(1, "a turn or a heading", c))
+                    }
+                    (None, _) => return Err(ParseError::unexpected_end(1, "a move")),
+                }
+            }
+        };
+        let dist = parse_number(&s[len..], len + 1)?;
+        Ok(Move(turn, dist))
     }
 }
 
-impl<'a> From<&'a str> for Move {
+impl<T: Coord> FromStr for Move<T> {
+    type Err = ParseError;
+
     /// Parse a move from a string
-    fn from(s: &'a str) -> Self {
-        let (direction, dist) = s.split_at(1);
-        let turn = match direction {
-            "L" => Turn::Left,
-            "R" => Turn::Right,
-            _ => panic!("Invalid move format!"),
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Move::parse_with(s, Grammar::Standard)
+    }
+}
+
+/// A route through the city, as given by the instructions on the
+/// Easter Bunny Recruiting Document. Routes start at the origin
+/// facing north.
+///
+/// Every position along a route fits in its coordinate type, routes
+/// which would leave it are rejected when parsed.
+#[derive(Debug,PartialEq)]
+pub struct Route<T = i32> {
+    moves: Vec<Move<T>>,
+    grammar: Grammar,
+}
+
+/// Iterator over each position visited along a `Route`.
+pub struct Positions<'a, T: 'a> {
+    moves: slice::Iter<'a, Move<T>>,
+    direction: Direction,
+    pos: Position<T>,
+    remaining: T,
+    started: bool,
+}
+
+impl<T: Coord> FromStr for Route<T> {
+    type Err = ParseError;
+
+    /// Parse a route from a comma-separated list of moves.
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Route::parse_with(s, Grammar::Standard)
+    }
+}
+
+impl<T: Coord> Route<T> {
+    /// Parse a route from a comma-separated list of moves using the
+    /// given grammar.
+    pub fn parse_with(s: &str, grammar: Grammar) -> Result<Self, ParseError> {
+        let mut moves = Vec::new();
+        let mut column = 0;
+        let mut direction = Direction::North;
+        let mut pos = Position::new();
+        for part in s.split(',') {
+            let trimmed = part.trim_start();
+            let offset = column + column_at(part, trimmed) - 1;
+            let token = trimmed.trim_end();
+            let m = Move::parse_with(token, grammar).map_err(|e| e.offset_by(offset))?;
+            direction = direction.turn(m.turn());
+            pos = pos.move_by(&direction, m.dist())
+                .map_err(|_| {
+                    ParseError::unexpected(offset + 1, "a move which keeps the route in range", token)
+                })?;
+            moves.push(m);
+            column += part.chars().count() + 1;
+        }
+        Ok(Route { moves, grammar })
+    }
+
+    /// Get the metric distances along this route are measured with.
+    pub fn metric(&self) -> Metric {
+        self.grammar.metric()
+    }
+
+    /// Get the moves which make up the route.
+    pub fn moves(&self) -> &[Move<T>] {
+        &self.moves
+    }
+
+    /// Get an iterator over every position visited along the route,
+    /// one block at a time. The first position is the origin.
+    pub fn positions(&self) -> Positions<'_, T> {
+        Positions {
+            moves: self.moves.iter(),
+            direction: Direction::North,
+            pos: Position::new(),
+            remaining: T::zero(),
+            started: false,
+        }
+    }
+
+    /// Get the position at the end of the route.
+    pub fn end(&self) -> Position<T> {
+        self.moves
+            .iter()
+            .fold((Direction::North, Position::new()), |(direction, pos), m| {
+                let direction = direction.turn(m.turn());
+                let pos = pos.move_by(&direction, m.dist())
+                    .expect("routes are checked when parsed");
+                (direction, pos)
+            })
+            .1
+    }
+
+    /// Get the first position which is visited twice, if any.
+    pub fn first_revisit(&self) -> Option<Position<T>> {
+        let mut visited = HashSet::new();
+        self.positions().find(|pos| !visited.insert(pos.clone()))
+    }
+
+    /// Get the first position which is visited twice, if any, by
+    /// intersecting the straight segments of the route.
+    ///
+    /// This agrees with `first_revisit`, but never walks the route
+    /// one block at a time. It takes time proportional to the square
+    /// of the number of moves, whatever their distances. Comparing
+    /// far apart segments can overflow the coordinate type even
+    /// though the route itself fits.
+    pub fn first_revisit_by_segments(&self) -> Result<Option<Position<T>>, Overflow> {
+        let mut segments: Vec<Segment<T>> = Vec::new();
+        let mut direction = Direction::North;
+        let mut pos = Position::new();
+        for m in &self.moves {
+            direction = direction.turn(m.turn());
+            if m.dist() == T::zero() {
+                continue;
+            }
+            let segment = Segment {
+                start: pos.clone(),
+                direction,
+                len: m.dist(),
+            };
+            // The start of each segment is where the last one
+            // finished, so only look for steps after it.
+            let overlaps = segments.iter()
+                .map(|earlier| segment.first_overlap(earlier))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(steps) = overlaps.into_iter().flatten().min() {
+                return segment.start.move_by(&direction, steps).map(Some);
+            }
+            pos = pos.move_by(&direction, m.dist())?;
+            segments.push(segment);
+        }
+        Ok(None)
+    }
+}
+
+impl<T: Coord> Segment<T> {
+    /// Find the first step along this segment, not counting its
+    /// start, which lands on a position covered by `other`.
+    fn first_overlap(&self, other: &Segment<T>) -> Result<Option<T>, Overflow> {
+        let d = self.direction.step();
+        let e = other.direction.step();
+        let offset = (other.start.0.checked_sub(&self.start.0).ok_or(Overflow)?,
+                      other.start.1.checked_sub(&self.start.1).ok_or(Overflow)?);
+        // The dot product of the offset with a step.
+        let dot = |step: (i8, i8)| {
+            scale(&offset.0, step.0)?.checked_add(&scale(&offset.1, step.1)?).ok_or(Overflow)
+        };
+        // The cross product of the offset with a step.
+        let cross = |step: (i8, i8)| dot((step.1, -step.0));
+
+        let (lo, hi) = match d.0 * e.1 - d.1 * e.0 {
+            0 if cross(d)? != T::zero() => return Ok(None),
+            0 => {
+                // Collinear: find the range of steps along this
+                // segment which the other one covers.
+                let from = match divide(&dot(d)?, d.0 * d.0 + d.1 * d.1)? {
+                    Some(from) => from,
+                    None => return Ok(None),
+                };
+                let to = if e == d {
+                    from.checked_add(&other.len)
+                } else {
+                    from.checked_sub(&other.len)
+                };
+                let to = to.ok_or(Overflow)?;
+                (from.clone().min(to.clone()), from.max(to))
+            }
+            det => {
+                // Crossing: solve `self.start + t.d = other.start + s.e`
+                // for whole numbers of steps `t` and `s`.
+                match (divide(&cross(e)?, det)?, divide(&cross(d)?, det)?) {
+                    (Some(t), Some(s)) if s >= T::zero() && s <= other.len => (t.clone(), t),
+                    _ => return Ok(None),
+                }
+            }
         };
-        let dist = dist.parse::<i32>().unwrap();
-        Move(turn, dist)
+        let first = lo.max(T::one());
+        Ok(if first <= hi.min(self.len.clone()) {
+            Some(first)
+        } else {
+            None
+        })
+    }
+}
+
+impl<'a, T: Coord> Iterator for Positions<'a, T> {
+    type Item = Position<T>;
+
+    fn next(&mut self) -> Option<Position<T>> {
+        if !self.started {
+            self.started = true;
+            return Some(self.pos.clone());
+        }
+        while self.remaining <= T::zero() {
+            let m = self.moves.next()?;
+            self.direction = self.direction.turn(m.turn());
+            self.remaining = m.dist();
+        }
+        self.remaining = self.remaining.checked_sub(&T::one()).expect("remaining steps are positive");
+        self.pos = self.pos.move_by(&self.direction, T::one()).expect("routes are checked when parsed");
+        Some(self.pos.clone())
+    }
+}
+
+/// The largest number of cells `render_ascii` will draw.
+pub const MAX_ASCII_CELLS: u64 = 1 << 24;
+
+/// The reason a path can't be rendered.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RenderError {
+    /// A coordinate, or the size of the drawing, is too large to
+    /// calculate.
+    Overflow,
+    /// The drawing would have more than `MAX_ASCII_CELLS` cells.
+    TooLarge,
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RenderError::Overflow => write!(f, "drawing size overflow"),
+            RenderError::TooLarge => {
+                write!(f, "drawing would have more than {} cells", MAX_ASCII_CELLS)
+            }
+        }
+    }
+}
+
+impl Error for RenderError {}
+
+/// A position as northing and easting, widened so that the size of a
+/// drawing can be worked out.
+type Point = (i128, i128);
+
+/// Widen a list of positions for drawing.
+fn points<T: Coord>(positions: &[Position<T>]) -> Result<Vec<Point>, RenderError> {
+    positions.iter()
+        .map(|pos| match (pos.0.to_i128(), pos.1.to_i128()) {
+            (Some(north), Some(east)) => Ok((north, east)),
+            _ => Err(RenderError::Overflow),
+        })
+        .collect()
+}
+
+/// Get the bounding box of a list of points, as the minimum and
+/// maximum northings and eastings.
+fn bounds(points: &[Point]) -> Option<(Point, Point)> {
+    let first = *points.first()?;
+    Some(points.iter().fold((first, first), |(min, max), pos| {
+        ((min.0.min(pos.0), min.1.min(pos.1)), (max.0.max(pos.0), max.1.max(pos.1)))
+    }))
+}
+
+/// Get the number of blocks from `min` to `max`, plus a margin.
+fn span(min: i128, max: i128, margin: i128) -> Result<i128, RenderError> {
+    max.checked_sub(min)
+        .and_then(|span| span.checked_add(margin))
+        .ok_or(RenderError::Overflow)
+}
+
+/// Find the first point in a list which appears earlier in it.
+fn find_revisit(points: &[Point]) -> Option<&Point> {
+    let mut visited = HashSet::new();
+    points.iter().find(|pos| !visited.insert(*pos))
+}
+
+/// Render a path as ASCII art, with north at the top.
+///
+/// Each visited position is drawn as `#` on a grid of `.` which just
+/// covers the path. The start is marked `S`, the end `E` and the
+/// first position visited twice `X`, in that order, so later marks
+/// win where they coincide. Paths which would need more than
+/// `MAX_ASCII_CELLS` cells aren't drawn.
+pub fn render_ascii<T: Coord>(positions: &[Position<T>]) -> Result<String, RenderError> {
+    let points = points(positions)?;
+    let (min, max) = match bounds(&points) {
+        Some(bounds) => bounds,
+        None => return Ok(String::new()),
+    };
+    let width = span(min.1, max.1, 1)?;
+    let height = span(min.0, max.0, 1)?;
+    if width.checked_mul(height).is_none_or(|cells| cells > i128::from(MAX_ASCII_CELLS)) {
+        return Err(RenderError::TooLarge);
+    }
+    let mut grid = vec![vec!['.'; width as usize]; height as usize];
+    {
+        let mut mark = |pos: &Point, c: char| {
+            grid[(max.0 - pos.0) as usize][(pos.1 - min.1) as usize] = c;
+        };
+        for pos in &points {
+            mark(pos, '#');
+        }
+        mark(&points[0], 'S');
+        mark(&points[points.len() - 1], 'E');
+        if let Some(pos) = find_revisit(&points) {
+            mark(pos, 'X');
+        }
+    }
+    Ok(grid.into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Render a path as a standalone SVG image.
+///
+/// The path is drawn as a polyline, with north at the top, inside a
+/// view box which covers the path with a margin of one block. The
+/// start, end, and first position visited twice are marked with
+/// green, red and blue circles respectively.
+pub fn render_svg<T: Coord>(positions: &[Position<T>]) -> Result<String, RenderError> {
+    let points = points(positions)?;
+    let (min, max) = match bounds(&points) {
+        Some(bounds) => bounds,
+        None => return Ok("<svg xmlns=\"http://www.w3.org/2000/svg\"/>\n".to_string()),
+    };
+    // Only the corners of the path need to be drawn.
+    let corners = points.iter()
+        .enumerate()
+        .filter(|&(i, pos)| {
+            if i == 0 || i == points.len() - 1 {
+                return true;
+            }
+            let (prev, next) = (&points[i - 1], &points[i + 1]);
+            (pos.0 - prev.0, pos.1 - prev.1) != (next.0 - pos.0, next.1 - pos.1)
+        })
+        .map(|(_, pos)| format!("{},{}", pos.1, -pos.0))
+        .collect::<Vec<_>>();
+    let circle = |pos: &Point, colour: &str| {
+        format!("  <circle cx=\"{}\" cy=\"{}\" r=\"0.4\" fill=\"{}\"/>\n",
+                pos.1,
+                -pos.0,
+                colour)
+    };
+
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+                          min.1 - 1,
+                          -max.0 - 1,
+                          span(min.1, max.1, 2)?,
+                          span(min.0, max.0, 2)?);
+    svg.push_str(&format!("  <polyline points=\"{}\" fill=\"none\" stroke=\"black\" \
+                           stroke-width=\"0.2\"/>\n",
+                          corners.join(" ")));
+    svg.push_str(&circle(&points[0], "green"));
+    svg.push_str(&circle(&points[points.len() - 1], "red"));
+    if let Some(pos) = find_revisit(&points) {
+        svg.push_str(&circle(pos, "blue"));
+    }
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+impl<T: Coord + 'static> Solution for Route<T> {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        input.parse()
+    }
+
+    fn part1(&self) -> Box<dyn fmt::Display> {
+        match self.end().distance(self.metric()) {
+            Ok(dist) => Box::new(dist),
+            Err(e) => Box::new(e),
+        }
+    }
+
+    fn part2(&self) -> Box<dyn fmt::Display> {
+        let revisit = self.first_revisit_by_segments()
+            .and_then(|pos| pos.map(|pos| pos.distance(self.metric())).transpose());
+        match revisit {
+            Ok(Some(dist)) => Box::new(dist),
+            Ok(None) => Box::new("no location visited twice"),
+            Err(e) => Box::new(e),
+        }
     }
 }
 
@@ -119,29 +751,322 @@ mod test {
         assert_eq!(Direction::South, Direction::East.turn(Turn::Right));
     }
 
+    #[test]
+    fn test_turn_extended() {
+        assert_eq!(Direction::South, Direction::North.turn(Turn::Reverse));
+        assert_eq!(Direction::SouthWest, Direction::NorthEast.turn(Turn::Reverse));
+        assert_eq!(Direction::West, Direction::West.turn(Turn::Forward));
+        assert_eq!(Direction::NorthWest, Direction::NorthEast.turn(Turn::Left));
+        assert_eq!(Direction::SouthEast, Direction::NorthEast.turn(Turn::Right));
+        assert_eq!(Direction::NorthEast, Direction::NorthWest.turn(Turn::Right));
+        assert_eq!(Direction::SouthWest, Direction::East.turn(Turn::Face(Direction::SouthWest)));
+    }
+
     #[test]
     fn move_in_direction() {
         let pos = Position(0, 0);
-        assert_eq!(Position(1, 0), pos.move_by(&Direction::North, 1));
-        assert_eq!(Position(10, 0), pos.move_by(&Direction::North, 10));
-        assert_eq!(Position(-7, 0), pos.move_by(&Direction::South, 7));
-        assert_eq!(Position(-7, 5),
+        assert_eq!(Ok(Position(1, 0)), pos.move_by(&Direction::North, 1));
+        assert_eq!(Ok(Position(10, 0)), pos.move_by(&Direction::North, 10));
+        assert_eq!(Ok(Position(-7, 0)), pos.move_by(&Direction::South, 7));
+        assert_eq!(Ok(Position(-7, 5)),
                    pos.move_by(&Direction::South, 7)
-                       .move_by(&Direction::East, 10)
-                       .move_by(&Direction::West, 5));
+                       .and_then(|pos| pos.move_by(&Direction::East, 10))
+                       .and_then(|pos| pos.move_by(&Direction::West, 5)));
+    }
+
+    #[test]
+    fn move_diagonally() {
+        let pos = Position(0, 0);
+        assert_eq!(Ok(Position(3, 3)), pos.move_by(&Direction::NorthEast, 3));
+        assert_eq!(Ok(Position(-2, 2)), pos.move_by(&Direction::SouthEast, 2));
+        assert_eq!(Ok(Position(-1, -1)), pos.move_by(&Direction::SouthWest, 1));
+        assert_eq!(Ok(Position(4, -4)), pos.move_by(&Direction::NorthWest, 4));
+    }
+
+    #[test]
+    fn move_overflows() {
+        assert_eq!(Err(Overflow), Position(i32::MAX, 0).move_by(&Direction::North, 1));
+        assert_eq!(Err(Overflow), Position(0, i32::MIN + 1).move_by(&Direction::SouthWest, 2));
+        assert_eq!(Ok(Position(i64::from(i32::MAX) + 1, 0)),
+                   Position(i64::from(i32::MAX), 0).move_by(&Direction::North, 1));
+    }
+
+    #[test]
+    fn position_chebyshev_distance() {
+        assert_eq!(Ok(3), Position(3, 2).distance(Metric::Chebyshev));
+        assert_eq!(Ok(7), Position(-7, 3).distance(Metric::Chebyshev));
+        assert_eq!(Ok(10), Position(-10, -10).distance(Metric::Chebyshev));
+        assert_eq!(Ok(20), Position(-10, -10).distance(Metric::Manhattan));
     }
 
     #[test]
     fn position_travel_distance() {
-        assert_eq!(5, Position(3, 2).travel_dist());
-        assert_eq!(5, Position(3, -2).travel_dist());
-        assert_eq!(10, Position(-7, 3).travel_dist());
-        assert_eq!(13, Position(-10, -3).travel_dist());
+        assert_eq!(Ok(5), Position(3, 2).travel_dist());
+        assert_eq!(Ok(5), Position(3, -2).travel_dist());
+        assert_eq!(Ok(10), Position(-7, 3).travel_dist());
+        assert_eq!(Ok(13), Position(-10, -3).travel_dist());
+    }
+
+    #[test]
+    fn distance_overflows() {
+        assert_eq!(Err(Overflow), Position(i32::MAX, 1).travel_dist());
+        assert_eq!(Err(Overflow), Position(i32::MIN, 0).distance(Metric::Chebyshev));
+        assert_eq!(Ok(i32::MAX), Position(i32::MAX, -5).distance(Metric::Chebyshev));
+        assert_eq!(Ok(i128::from(i64::MAX) * 2),
+                   Position(i128::from(i64::MAX), i128::from(i64::MIN) + 1).travel_dist());
     }
 
     #[test]
     fn parse_moves() {
-        assert_eq!(Move(Turn::Left, 10), Move::from("L10"));
-        assert_eq!(Move(Turn::Right, 100), Move::from("R100"));
+        assert_eq!(Ok(Move(Turn::Left, 10)), "L10".parse());
+        assert_eq!(Ok(Move(Turn::Right, 100)), "R100".parse());
+    }
+
+    #[test]
+    fn parse_extended_moves() {
+        let parse = |s| Move::parse_with(s, Grammar::Extended);
+        assert_eq!(Ok(Move(Turn::Left, 10)), parse("L10"));
+        assert_eq!(Ok(Move(Turn::Reverse, 2)), parse("B2"));
+        assert_eq!(Ok(Move(Turn::Forward, 7)), parse("F7"));
+        assert_eq!(Ok(Move(Turn::Face(Direction::North), 1)), parse("N1"));
+        assert_eq!(Ok(Move(Turn::Face(Direction::NorthEast), 12)), parse("NE12"));
+        assert_eq!(Ok(Move(Turn::Face(Direction::SouthWest), 3)), parse("SW3"));
+        assert_eq!(Ok(Move(Turn::Face(Direction::West), 3)), parse("W3"));
+        assert_eq!(Err(ParseError::unexpected_char(1, "a turn or a heading", 'X')),
+                   parse("X3"));
+        assert_eq!(Err(ParseError::unexpected_char(3, "a number", 'X')), parse("NWX"));
+        assert_eq!(Err(ParseError::unexpected_char(1, "'L' or 'R'", 'B')),
+                   "B2".parse::<Move>());
+    }
+
+    #[test]
+    fn parse_invalid_moves() {
+        assert_eq!(Err(ParseError::unexpected_char(1, "'L' or 'R'", 'U')),
+                   "U10".parse::<Move>());
+        assert_eq!(Err(ParseError::unexpected_end(2, "a number")),
+                   "L".parse::<Move>());
+        assert_eq!(Err(ParseError::unexpected_char(2, "a number", '-')),
+                   "R-4".parse::<Move>());
+        assert_eq!(Err(ParseError::unexpected_char(4, "a digit", 'x')),
+                   "R10x".parse::<Move>());
+        assert_eq!(Err(ParseError::unexpected_end(1, "a move")), "".parse::<Move>());
+        assert_eq!(Err(ParseError::unexpected_char(5, "'L' or 'R'", 'U')),
+                   "R1,\u{3000}U2".parse::<Route>());
+    }
+
+    fn route(s: &str) -> Route {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_route() {
+        assert_eq!(vec![Move(Turn::Right, 3), Move(Turn::Left, 5), Move(Turn::Right, 1)],
+                   route("R3, L5,R1").moves());
+        let err = "R1, L2, X3".parse::<Route>().err().unwrap();
+        assert_eq!(9, err.column());
+    }
+
+    #[test]
+    fn route_positions() {
+        assert_eq!(vec![Position(0, 0),
+                        Position(0, 1),
+                        Position(0, 2),
+                        Position(1, 2),
+                        Position(0, 2)],
+                   route("R2, L1, L0, L1").positions().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn route_end() {
+        assert_eq!(Position(3, 2), route("R2, L3").end());
+        assert_eq!(Position(-2, 0), route("R2, R2, R2").end());
+        assert_eq!(Ok(12), route("R5, L5, R5, R3").end().travel_dist());
+        assert_eq!(route("R5, L5, R5, R3").positions().last(),
+                   Some(route("R5, L5, R5, R3").end()));
+    }
+
+    #[test]
+    fn route_first_revisit() {
+        assert_eq!(Some(Position(0, 4)), route("R8, R4, R4, R8").first_revisit());
+        assert_eq!(Some(Position(0, 0)), route("R1, R1, R1, R1").first_revisit());
+        assert_eq!(None, route("R2, L3").first_revisit());
+    }
+
+    #[test]
+    fn route_first_revisit_by_segments() {
+        assert_eq!(Ok(Some(Position(0, 4))),
+                   route("R8, R4, R4, R8").first_revisit_by_segments());
+        assert_eq!(Ok(Some(Position(0, 0))),
+                   route("R1, R1, R1, R1").first_revisit_by_segments());
+        assert_eq!(Ok(None), route("R2, L3").first_revisit_by_segments());
+        assert_eq!(Ok(Some(Position(0, 1999999999))),
+                   route("R2000000000, R1, R1, R1").first_revisit_by_segments());
+    }
+
+    #[test]
+    fn large_routes() {
+        let moves = "L2000000000, R1, R2000000000, L0, R2000000000, R1";
+        assert_eq!(Err(Overflow), route(moves).first_revisit_by_segments());
+        let wide = moves.parse::<Route<i64>>().unwrap();
+        assert_eq!(Ok(None), wide.first_revisit_by_segments());
+        assert_eq!(Position(0, 2000000000), wide.end());
+
+        let moves = "R2000000000, R0, L200000000";
+        assert_eq!(Err(ParseError::unexpected(18,
+                                              "a move which keeps the route in range",
+                                              "L200000000")),
+                   moves.parse::<Route>());
+        let wide = moves.parse::<Route<i64>>().unwrap();
+        assert_eq!(Position(0, 2200000000), wide.end());
+        assert_eq!(Ok(2200000000), wide.end().travel_dist());
+        assert_eq!("2200000000", wide.part1().to_string());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big_routes() {
+        let huge = "1000000000000000000000000000000000000000";
+        let route = format!("R{}, L1, L{}", huge, huge).parse::<Route<BigInt>>().unwrap();
+        assert_eq!(Position(BigInt::from(1), BigInt::from(0)), route.end());
+        let route = format!("R{}, R{}", huge, huge).parse::<Route<BigInt>>().unwrap();
+        assert_eq!(Ok(format!("2{}", &huge[1..]).parse().unwrap()), route.end().travel_dist());
+    }
+
+    #[test]
+    fn segments_agree_with_positions() {
+        let routes = ["R8, R4, R4, R8",
+                      "R5, L5, R5, R3",
+                      "L3, L0, R2, R0, R5, R2, R1",
+                      "R3, R3, R3, R3, R3",
+                      "R4, R2, R2, R4",
+                      "L2, L2, L0, L2, L2, R0, R0, L2"];
+        for r in routes.iter() {
+            let route = route(r);
+            assert_eq!(Ok(route.first_revisit()), route.first_revisit_by_segments(), "{}", r);
+        }
+
+        // A longer, pseudo-random, walk.
+        let mut seed = 42u32;
+        let moves = (0..200)
+            .map(|_| {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                let turn = if seed & 0x10000 == 0 { "L" } else { "R" };
+                format!("{}{}", turn, (seed >> 20) % 12)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let route = route(&moves);
+        assert!(route.first_revisit().is_some());
+        assert_eq!(Ok(route.first_revisit()), route.first_revisit_by_segments());
+    }
+
+    #[test]
+    fn extended_routes() {
+        let route = Route::parse_with("NE3, B1, L2, F1, S4", Grammar::Extended).unwrap();
+        assert_eq!(Metric::Chebyshev, route.metric());
+        assert_eq!(Position(-5, 5), route.end());
+        assert_eq!(Some(Position(2, 2)), route.first_revisit());
+        assert_eq!(Ok(5), route.end().distance(route.metric()));
+    }
+
+    #[test]
+    fn segments_agree_with_positions_on_extended_routes() {
+        let routes = ["NE3, B1, L2, F1, S4",
+                      "N4, SE2, SW2, E3",
+                      "NE2, E2, NW2, S5",
+                      "NE4, NW1, SE2, B3",
+                      "F2, R2, NW3, SW3, E1"];
+        for r in routes.iter() {
+            let route: Route = Route::parse_with(r, Grammar::Extended).unwrap();
+            assert_eq!(Ok(route.first_revisit()), route.first_revisit_by_segments(), "{}", r);
+        }
+
+        let headings = ["L", "R", "B", "F", "N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+        let mut seed = 7u32;
+        let moves = (0..200)
+            .map(|_| {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                let heading = headings[(seed >> 16) as usize % headings.len()];
+                format!("{}{}", heading, (seed >> 24) % 9)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let route: Route = Route::parse_with(&moves, Grammar::Extended).unwrap();
+        assert!(route.first_revisit().is_some());
+        assert_eq!(Ok(route.first_revisit()), route.first_revisit_by_segments());
+    }
+
+    #[test]
+    fn render_route_ascii() {
+        let positions = route("R3, L2, L1, L1, L2").positions().collect::<Vec<_>>();
+        assert_eq!(Ok("..##.
+..#XE
+S###.".to_string()),
+                   render_ascii(&positions));
+        let positions = route("L2, R1").positions().collect::<Vec<_>>();
+        assert_eq!(Ok("E..
+##S".to_string()),
+                   render_ascii(&positions));
+        assert_eq!(Ok(String::new()), render_ascii::<i32>(&[]));
+    }
+
+    #[test]
+    fn render_far_apart() {
+        let start = Position::new();
+        let positions = [start.move_by(&Direction::West, 2_000_000_000).unwrap(),
+                         start.move_by(&Direction::East, 2_000_000_000).unwrap()];
+        assert_eq!(Err(RenderError::TooLarge), render_ascii(&positions));
+        let svg = render_svg(&positions).unwrap();
+        assert!(svg.contains("viewBox=\"-2000000001 -1 4000000002 2\""));
+        let corner = start.move_by(&Direction::North, 5000)
+            .and_then(|pos| pos.move_by(&Direction::East, 5000))
+            .unwrap();
+        let corners = [start, corner];
+        assert_eq!(Err(RenderError::TooLarge), render_ascii(&corners));
+    }
+
+    #[test]
+    fn render_wide_coordinates() {
+        let start = Position::<i64>::new();
+        let far = start.move_by(&Direction::North, 8_000_000_000).unwrap();
+        let positions = [start, far];
+        let svg = render_svg(&positions).unwrap();
+        assert!(svg.contains("viewBox=\"-1 -8000000001 2 8000000002\""));
+        assert!(svg.contains("points=\"0,0 0,-8000000000\""));
+        assert_eq!(Err(RenderError::TooLarge), render_ascii(&positions));
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn render_huge_coordinates() {
+        let start = Position::<BigInt>::new();
+        let far = start.move_by(&Direction::East, BigInt::from(1) << 200).unwrap();
+        let positions = [start, far];
+        assert_eq!(Err(RenderError::Overflow), render_ascii(&positions));
+        assert_eq!(Err(RenderError::Overflow), render_svg(&positions));
+    }
+
+    #[test]
+    fn render_route_svg() {
+        let positions = route("R3, L2, L1, L1, L2").positions().collect::<Vec<_>>();
+        let svg = render_svg(&positions).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -3 6 4\">"));
+        assert!(svg.contains("points=\"0,0 3,0 3,-2 2,-2 2,-1 4,-1\""));
+        assert!(svg.contains("<circle cx=\"0\" cy=\"0\" r=\"0.4\" fill=\"green\"/>"));
+        assert!(svg.contains("<circle cx=\"4\" cy=\"-1\" r=\"0.4\" fill=\"red\"/>"));
+        assert!(svg.contains("<circle cx=\"3\" cy=\"-1\" r=\"0.4\" fill=\"blue\"/>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn solve_examples() {
+        assert_eq!("12", Route::<i32>::parse("R5, L5, R5, R3").unwrap().part1().to_string());
+        assert_eq!("4", Route::<i32>::parse("R8, R4, R4, R8").unwrap().part2().to_string());
+        let long = "R800000000, R400000000, R400000000, R800000000";
+        assert_eq!("400000000", Route::<i64>::parse(long).unwrap().part2().to_string());
+        let moves = "L2000000000, R1, R2000000000, L0, R2000000000, R1";
+        assert_eq!("coordinate overflow", Route::<i32>::parse(moves).unwrap().part2().to_string());
+        assert_eq!("no location visited twice",
+                   Route::<i64>::parse(moves).unwrap().part2().to_string());
     }
 }
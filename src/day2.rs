@@ -1,34 +1,115 @@
 //! Day 2
 
-use std::marker::PhantomData;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::BufRead;
+
+use super::{ParseError, ReadError, Solution};
 
 pub trait Keypad
     where Self: Clone
 {
     /// Check if the given position is valid on this keypad.
-    fn is_on_keypad(pos: &KeypadPos<Self>) -> bool;
+    fn is_on_keypad(&self, pos: &KeypadPos<'_, Self>) -> bool;
 
     /// Get the starting position on the Keypad.
     ///
     /// Returns a new `KeypadPos` on this `Keypad` pointing at the
-    /// digit '5'.
-    fn start_pos() -> KeypadPos<Self>;
+    /// starting key. For the puzzle's keypads this is the digit '5'.
+    fn start_pos(&self) -> KeypadPos<'_, Self>;
+
+    /// Get the label of the key at the given position
+    fn label_at(&self, pos: &KeypadPos<'_, Self>) -> &str;
+
+    /// Get where a move which would leave the keypad from `pos`
+    /// ends up. By default the move is ignored.
+    fn off_edge<'k>(&'k self,
+                    pos: &KeypadPos<'k, Self>,
+                    _dir: Direction)
+                    -> (KeypadPos<'k, Self>, Outcome) {
+        (pos.clone(), Outcome::Ignored)
+    }
+
+    /// Get the cost of moving on to the key at the given position.
+    /// Used when searching for instructions. By default every key
+    /// costs 1.
+    fn weight_at(&self, _pos: &KeypadPos<'_, Self>) -> usize {
+        1
+    }
+
+    /// Get the PIN from a set of instructions, one line per key.
+    /// Lines may end with either `\n` or `\r\n`, and a line ending
+    /// after the last line doesn't start another key.
+    fn pin_from(&self, instructions: &str) -> Result<Vec<String>, ParseError> {
+        instructions.lines()
+            .enumerate()
+            .scan(self.start_pos(), |pos, (i, line)| {
+                Some(pos.move_by(line)
+                    .map(|next| {
+                        *pos = next;
+                        pos.label().to_string()
+                    })
+                    .map_err(|e| e.at_line(i + 1)))
+            })
+            .collect()
+    }
+
+    /// Read the PIN from a stream of instructions, one key at a time.
+    ///
+    /// Lines may end with either `\n` or `\r\n`. Unlike `pin_from`,
+    /// blank lines are skipped rather than entering the same key
+    /// again.
+    fn pin_reader<R: BufRead>(&self, reader: R) -> PinReader<'_, Self, R> {
+        PinReader {
+            reader,
+            pos: self.start_pos(),
+            line: String::new(),
+            line_number: 0,
+        }
+    }
 
-    /// Get the digit at the given position
-    fn digit_at(pos: &KeypadPos<Self>) -> char;
+    /// Read the whole PIN from a stream of instructions.
+    fn pin_from_reader<R: BufRead>(&self, reader: R) -> Result<Vec<String>, ReadError> {
+        self.pin_reader(reader).collect()
+    }
 
-    fn pin_from(instructions: &str) -> String {
-        instructions.split("\n")
-            .scan(Self::start_pos(), |pos, line| {
-                *pos = pos.move_by(line);
-                Some(pos.digit())
+    /// Trace each move made by a set of instructions, one line per
+    /// key. The label for each line is the one at the last step of
+    /// its trace, or the previous line's label if it is empty.
+    fn trace_from(&self, instructions: &str) -> Result<Vec<Vec<Step<'_, Self>>>, ParseError> {
+        let mut pos = self.start_pos();
+        instructions.lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let steps = pos.trace(line).map_err(|e| e.at_line(i + 1))?;
+                if let Some(last) = steps.last() {
+                    pos = last.pos.clone();
+                }
+                Ok(steps)
             })
-            .collect::<String>()
+            .collect()
+    }
+
+    /// Get the cheapest instructions which enter a PIN on this
+    /// keypad, one line per key. Unless the keys are weighted these
+    /// are the instructions with the fewest moves. Returns `None` if
+    /// the PIN can't be entered.
+    fn instructions_for<S: AsRef<str>>(&self, pin: &[S]) -> Option<Vec<String>> {
+        search_instructions(self, pin, false).into_iter().next()
+    }
+
+    /// Get every set of instructions which enters a PIN on this
+    /// keypad as cheaply as possible. The number of these grows
+    /// quickly with the length of the PIN.
+    fn all_instructions_for<S: AsRef<str>>(&self, pin: &[S]) -> Vec<Vec<String>> {
+        search_instructions(self, pin, true)
     }
 }
 
 /// Direction (up, down, left, right)
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Direction {
     Up,
     Down,
@@ -36,12 +117,47 @@ pub enum Direction {
     Right,
 }
 
+/// The outcome of a single move on a keypad
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Outcome {
+    /// Moved to the next key.
+    Moved,
+    /// Stayed put, as the move would have left the keypad.
+    Ignored,
+    /// Left the keypad and came back on the far side.
+    Wrapped,
+    /// Left the keypad and jumped to another key.
+    Teleported,
+}
+
+/// What happens when a move would leave a `GridKeypad`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Edge {
+    /// Stay put, as on the puzzle's keypads.
+    Saturate,
+    /// Carry on from the far side of the keypad, as if it were
+    /// wrapped around a torus. Only moves off the grid the keypad is
+    /// laid out on wrap, skipping any holes on the far side; moves into
+    /// holes inside the grid are ignored.
+    Wrap,
+    /// Jump to the first key with the given label.
+    Teleport(String),
+}
+
+/// A single move in a trace, and the position it ended at.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Step<'a, K: Keypad + 'a> {
+    pub direction: Direction,
+    pub pos: KeypadPos<'a, K>,
+    pub outcome: Outcome,
+}
+
 /// Keypad Position
 #[derive(Debug, PartialEq, Clone)]
-pub struct KeypadPos<K: ?Sized + Keypad + Clone> {
+pub struct KeypadPos<'a, K: Keypad + 'a> {
+    keypad: &'a K,
     accross: i32,
     down: i32,
-    _keypad: PhantomData<K>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -50,52 +166,132 @@ pub struct NineDigitKeypad;
 #[derive(Debug, PartialEq, Clone)]
 pub struct DiamondKeypad;
 
-impl From<char> for Direction {
-    /// Convert a string into a `Direction`
-    fn from(c: char) -> Self {
+/// Iterator over the keys of a PIN read from a stream of
+/// instructions. Created by `Keypad::pin_reader`.
+pub struct PinReader<'a, K: Keypad + 'a, R> {
+    reader: R,
+    pos: KeypadPos<'a, K>,
+    line: String,
+    line_number: usize,
+}
+
+/// A keypad built from a text layout.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GridKeypad {
+    rows: Vec<Vec<Option<String>>>,
+    start: (i32, i32),
+    edge: Edge,
+    weights: HashMap<String, usize>,
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseError;
+
+    /// Convert a character into a `Direction`
+    fn try_from(c: char) -> Result<Self, ParseError> {
         match c {
-            'U' => Direction::Up,
-            'D' => Direction::Down,
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => panic!("Invalid direction {}", c),
+            'U' => Ok(Direction::Up),
+            'D' => Ok(Direction::Down),
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(ParseError::unexpected_char(1, "one of 'U', 'D', 'L' or 'R'", c)),
+        }
+    }
+}
+
+impl From<Direction> for char {
+    /// Convert a `Direction` back into its instruction character
+    fn from(dir: Direction) -> char {
+        match dir {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
         }
     }
 }
 
-impl<K> KeypadPos<K>
-    where K: Keypad + Clone
+impl Direction {
+    /// All directions, in the order they are tried when searching
+    /// for instructions.
+    const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    /// Get the change accross and down for a single move in this
+    /// direction.
+    fn offset(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+impl<'a, K> KeypadPos<'a, K>
+    where K: Keypad
 {
     /// Move to the next key
     pub fn move_key(&self, dir: Direction) -> Self {
-        let next_pos = match dir {
-            Direction::Up => KeypadPos { down: self.down - 1, ..*self },
-            Direction::Down => KeypadPos { down: self.down + 1, ..*self },
-            Direction::Left => KeypadPos { accross: self.accross - 1, ..*self },
-            Direction::Right => KeypadPos { accross: self.accross + 1, ..*self },
+        self.step(dir).0
+    }
+
+    /// Move to the next key, and report what happened. Moves which
+    /// would leave the keypad are handled by `Keypad::off_edge`.
+    pub fn step(&self, dir: Direction) -> (Self, Outcome) {
+        let (accross, down) = dir.offset();
+        let next_pos = KeypadPos {
+            accross: self.accross + accross,
+            down: self.down + down,
+            ..*self
         };
-        if K::is_on_keypad(&next_pos) {
-            next_pos
+        if self.keypad.is_on_keypad(&next_pos) {
+            (next_pos, Outcome::Moved)
         } else {
-            self.clone()
+            self.keypad.off_edge(self, dir)
         }
     }
 
     /// Move by a direction string
-    pub fn move_by(&self, directions: &str) -> Self {
-        directions.chars()
-            .map(|c| Direction::from(c))
-            .fold(self.clone(), |pos, dir| pos.move_key(dir))
+    pub fn move_by(&self, directions: &str) -> Result<Self, ParseError> {
+        parse_line(directions).map(|directions| self.follow(&directions))
     }
 
-    /// Get the digit at the keypad's current position.
-    pub fn digit(&self) -> char {
-        K::digit_at(self)
+    /// Trace each move of a direction string.
+    pub fn trace(&self, directions: &str) -> Result<Vec<Step<'a, K>>, ParseError> {
+        let directions = parse_line(directions)?;
+        Ok(directions.iter()
+            .scan(self.clone(), |pos, &direction| {
+                let (next, outcome) = pos.step(direction);
+                *pos = next.clone();
+                Some(Step {
+                    direction,
+                    pos: next,
+                    outcome,
+                })
+            })
+            .collect())
+    }
+
+    /// Move by each of a list of directions in turn.
+    fn follow(&self, directions: &[Direction]) -> Self {
+        directions.iter().fold(self.clone(), |pos, dir| pos.move_key(*dir))
+    }
+
+    /// Get the label of the key at the keypad's current position.
+    pub fn label(&self) -> &'a str {
+        self.keypad.label_at(self)
+    }
+
+    /// Get the row and column of this position, for use as a key
+    /// when searching. Sorts in reading order.
+    fn coords(&self) -> (i32, i32) {
+        (self.down, self.accross)
     }
 }
 
 impl Keypad for NineDigitKeypad {
-    fn is_on_keypad(pos: &KeypadPos<Self>) -> bool {
+    fn is_on_keypad(&self, pos: &KeypadPos<'_, Self>) -> bool {
         if pos.accross < 0 || pos.down < 0 {
             return false;
         }
@@ -105,139 +301,698 @@ impl Keypad for NineDigitKeypad {
         true
     }
 
-    fn start_pos() -> KeypadPos<Self> {
+    fn start_pos(&self) -> KeypadPos<'_, Self> {
         KeypadPos {
+            keypad: self,
             accross: 1,
             down: 1,
-            _keypad: PhantomData,
         }
     }
 
-    fn digit_at(pos: &KeypadPos<Self>) -> char {
-        const DIGITS: [[char; 3]; 3] = [['1', '2', '3'], ['4', '5', '6'], ['7', '8', '9']];
+    fn label_at(&self, pos: &KeypadPos<'_, Self>) -> &str {
+        const DIGITS: [[&str; 3]; 3] = [["1", "2", "3"], ["4", "5", "6"], ["7", "8", "9"]];
         DIGITS[pos.down as usize][pos.accross as usize]
     }
 }
 
 impl Keypad for DiamondKeypad {
-    fn is_on_keypad(pos: &KeypadPos<Self>) -> bool {
+    fn is_on_keypad(&self, pos: &KeypadPos<'_, Self>) -> bool {
         let h = pos.accross - 2;
         let v = pos.down - 2;
         let abs = h.abs() + v.abs();
         abs <= 2
     }
 
-    fn start_pos() -> KeypadPos<Self> {
+    fn start_pos(&self) -> KeypadPos<'_, Self> {
         KeypadPos {
+            keypad: self,
             accross: 0,
             down: 2,
-            _keypad: PhantomData,
         }
     }
 
-    fn digit_at(pos: &KeypadPos<Self>) -> char {
-        const DIGITS: [[char; 5]; 5] = [[' ', ' ', '1', ' ', ' '],
-                                        [' ', '2', '3', '4', ' '],
-                                        ['5', '6', '7', '8', '9'],
-                                        [' ', 'A', 'B', 'C', ' '],
-                                        [' ', ' ', 'D', ' ', ' ']];
+    fn label_at(&self, pos: &KeypadPos<'_, Self>) -> &str {
+        const DIGITS: [[&str; 5]; 5] = [[" ", " ", "1", " ", " "],
+                                        [" ", "2", "3", "4", " "],
+                                        ["5", "6", "7", "8", "9"],
+                                        [" ", "A", "B", "C", " "],
+                                        [" ", " ", "D", " ", " "]];
         let digit = DIGITS[pos.down as usize][pos.accross as usize];
-        assert_ne!(digit, ' ', "at {:?}", pos);
+        assert_ne!(digit, " ", "at {:?}", pos);
         digit
     }
 }
 
+impl GridKeypad {
+    /// Create a keypad from a layout, starting at the first key
+    /// with the given label.
+    ///
+    /// Each line of the layout is a row of keys, with each character
+    /// being the label of a key. Spaces mark holes where there is no
+    /// key.
+    pub fn new(layout: &str, start: &str) -> Result<Self, ParseError> {
+        let rows = layout.lines()
+            .map(|line| {
+                line.chars()
+                    .map(|c| if c == ' ' { None } else { Some(c.to_string()) })
+                    .collect()
+            })
+            .collect();
+        Self::from_rows(layout, rows, start)
+    }
+
+    /// Create a keypad from a layout with labels of any length,
+    /// starting at the first key with the given label.
+    ///
+    /// Each line of the layout is a row of keys, with the labels
+    /// separated by `|`. Empty labels mark holes where there is no
+    /// key. For example `"7|8|9\n|0|ENTER"`.
+    pub fn from_cells(layout: &str, start: &str) -> Result<Self, ParseError> {
+        let rows = layout.lines()
+            .map(|line| {
+                line.split('|')
+                    .map(|cell| match cell.trim() {
+                        "" => None,
+                        label => Some(label.to_string()),
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::from_rows(layout, rows, start)
+    }
+
+    /// Set what happens when a move would leave the keypad.
+    pub fn with_edge(self, edge: Edge) -> Self {
+        GridKeypad { edge, ..self }
+    }
+
+    /// Set the cost of moving on to keys with the given label.
+    ///
+    /// # Panics
+    ///
+    /// If the weight is zero.
+    pub fn with_weight(mut self, label: &str, weight: usize) -> Self {
+        assert!(weight > 0, "key weights must be positive");
+        self.weights.insert(label.to_string(), weight);
+        self
+    }
+
+    /// Find the starting key and create the keypad.
+    fn from_rows(layout: &str,
+                 rows: Vec<Vec<Option<String>>>,
+                 start: &str)
+                 -> Result<Self, ParseError> {
+        let keypad = GridKeypad {
+            rows,
+            start: (0, 0),
+            edge: Edge::Saturate,
+            weights: HashMap::new(),
+        };
+        match keypad.find(start) {
+            Some(start) => Ok(GridKeypad { start, ..keypad }),
+            None => {
+                let column = layout.lines().last().map_or(0, |line| line.chars().count()) + 1;
+                Err(ParseError::unexpected_end(column, format!("a key labelled {:?}", start))
+                    .at_line(keypad.rows.len().max(1)))
+            }
+        }
+    }
+
+    /// Get the label of the key at a given position, if there is one.
+    fn key(&self, accross: i32, down: i32) -> Option<&str> {
+        if accross < 0 || down < 0 {
+            return None;
+        }
+        self.rows.get(down as usize)?.get(accross as usize)?.as_ref().map(|label| &label[..])
+    }
+
+    /// Find the position of the first key with the given label.
+    fn find(&self, label: &str) -> Option<(i32, i32)> {
+        self.rows.iter().enumerate().find_map(|(down, row)| {
+            row.iter()
+                .position(|key| key.as_ref().map(|key| &key[..]) == Some(label))
+                .map(|accross| (accross as i32, down as i32))
+        })
+    }
+}
+
+impl Keypad for GridKeypad {
+    fn is_on_keypad(&self, pos: &KeypadPos<'_, Self>) -> bool {
+        self.key(pos.accross, pos.down).is_some()
+    }
+
+    fn start_pos(&self) -> KeypadPos<'_, Self> {
+        KeypadPos {
+            keypad: self,
+            accross: self.start.0,
+            down: self.start.1,
+        }
+    }
+
+    fn label_at(&self, pos: &KeypadPos<'_, Self>) -> &str {
+        self.key(pos.accross, pos.down).expect("positions are always on the keypad")
+    }
+
+    fn off_edge<'k>(&'k self,
+                    pos: &KeypadPos<'k, Self>,
+                    dir: Direction)
+                    -> (KeypadPos<'k, Self>, Outcome) {
+        let (accross, down) = match self.edge {
+            Edge::Saturate => return (pos.clone(), Outcome::Ignored),
+            Edge::Wrap => {
+                let width = self.rows.iter().map(|row| row.len()).max().unwrap_or(0) as i32;
+                let height = self.rows.len() as i32;
+                let (step_accross, step_down) = dir.offset();
+                let (mut accross, mut down) = (pos.accross + step_accross, pos.down + step_down);
+                if (0..width).contains(&accross) && (0..height).contains(&down) {
+                    // A hole inside the grid, not its edge.
+                    return (pos.clone(), Outcome::Ignored);
+                }
+                // Keep going from the far side until we land on a key.
+                // At worst this is the one we started on.
+                loop {
+                    accross = accross.rem_euclid(width);
+                    down = down.rem_euclid(height);
+                    if self.key(accross, down).is_some() {
+                        break (accross, down);
+                    }
+                    accross += step_accross;
+                    down += step_down;
+                }
+            }
+            Edge::Teleport(ref label) => {
+                match self.find(label) {
+                    Some(key) => key,
+                    None => return (pos.clone(), Outcome::Ignored),
+                }
+            }
+        };
+        if (accross, down) == (pos.accross, pos.down) {
+            return (pos.clone(), Outcome::Ignored);
+        }
+        let outcome = match self.edge {
+            Edge::Wrap => Outcome::Wrapped,
+            _ => Outcome::Teleported,
+        };
+        (KeypadPos {
+             accross,
+             down,
+             ..*pos
+         },
+         outcome)
+    }
+
+    fn weight_at(&self, pos: &KeypadPos<'_, Self>) -> usize {
+        self.weights.get(self.label_at(pos)).cloned().unwrap_or(1)
+    }
+}
+
+impl<'a, K> Step<'a, K>
+    where K: Keypad
+{
+    /// Get the label of the key this step ended at.
+    pub fn label(&self) -> &'a str {
+        self.pos.label()
+    }
+}
+
+impl<'a, K, R> Iterator for PinReader<'a, K, R>
+    where K: Keypad,
+          R: BufRead
+{
+    type Item = Result<String, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => self.line_number += 1,
+                Err(e) => return Some(Err(e.into())),
+            }
+            let line = self.line.trim_end_matches('\n').trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(match self.pos.move_by(line) {
+                Ok(pos) => {
+                    self.pos = pos;
+                    Ok(self.pos.label().to_string())
+                }
+                Err(e) => Err(e.at_line(self.line_number).into()),
+            });
+        }
+    }
+}
+
+/// Parse a line of directions.
+fn parse_line(line: &str) -> Result<Vec<Direction>, ParseError> {
+    line.chars()
+        .enumerate()
+        .map(|(i, c)| Direction::try_from(c).map_err(|e| e.offset_by(i)))
+        .collect()
+}
+
+/// A position on a keypad, the cost of the cheapest paths which
+/// reach it, and those paths.
+type Paths<'a, K> = (KeypadPos<'a, K>, usize, Vec<Vec<Direction>>);
+
+/// A position on a keypad, the cost of the cheapest instructions
+/// which reach it, and those instructions.
+type Sequences<'a, K> = (KeypadPos<'a, K>, usize, Vec<Vec<String>>);
+
+/// Find the cheapest paths from a position to each key with the
+/// given label. Ignored moves never make a path cheaper, so are
+/// skipped. Unless `all` is set only the first path to each key is
+/// kept.
+fn paths_to<'a, K: Keypad>(from: &KeypadPos<'a, K>, label: &str, all: bool) -> Vec<Paths<'a, K>> {
+    let mut best: BTreeMap<_, Paths<'a, K>> = BTreeMap::new();
+    best.insert(from.coords(), (from.clone(), 0, vec![Vec::new()]));
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((0, from.coords())));
+    let mut settled = HashSet::new();
+    let mut found = Vec::new();
+    while let Some(Reverse((cost, coords))) = queue.pop() {
+        if !settled.insert(coords) {
+            continue;
+        }
+        let (pos, _, paths) = best[&coords].clone();
+        if pos.label() == label {
+            found.push((pos.clone(), cost, paths.clone()));
+        }
+        for &dir in Direction::ALL.iter() {
+            let (moved, outcome) = pos.step(dir);
+            if outcome == Outcome::Ignored || settled.contains(&moved.coords()) {
+                continue;
+            }
+            let moved_cost = cost + pos.keypad.weight_at(&moved);
+            let entry = best.entry(moved.coords())
+                .or_insert_with(|| (moved.clone(), moved_cost, Vec::new()));
+            if moved_cost < entry.1 {
+                entry.1 = moved_cost;
+                entry.2.clear();
+            }
+            if moved_cost > entry.1 || !(all || entry.2.is_empty()) {
+                continue;
+            }
+            entry.2.extend(paths.iter().map(|path| {
+                let mut path = path.clone();
+                path.push(dir);
+                path
+            }));
+            queue.push(Reverse((moved_cost, moved.coords())));
+        }
+    }
+    found
+}
+
+/// Search for the cheapest instructions which enter a PIN.
+///
+/// Where a label appears on more than one key the nearest one isn't
+/// always the best choice, so the cheapest way of reaching each key
+/// is kept after every label. Unless `all` is set only one set of
+/// instructions is kept for each key.
+fn search_instructions<K, S>(keypad: &K, pin: &[S], all: bool) -> Vec<Vec<String>>
+    where K: Keypad,
+          S: AsRef<str>
+{
+    let start = keypad.start_pos();
+    let mut states = BTreeMap::new();
+    states.insert(start.coords(), (start, 0, vec![Vec::new()]));
+    for label in pin {
+        let mut next: BTreeMap<_, Sequences<'_, K>> = BTreeMap::new();
+        for &(ref pos, cost, ref sequences) in states.values() {
+            for (end, path_cost, paths) in paths_to(pos, label.as_ref(), all) {
+                let cost = cost + path_cost;
+                let entry = next.entry(end.coords()).or_insert_with(|| (end, cost, Vec::new()));
+                if cost < entry.1 {
+                    entry.1 = cost;
+                    entry.2.clear();
+                }
+                if cost > entry.1 || !(all || entry.2.is_empty()) {
+                    continue;
+                }
+                for sequence in sequences {
+                    for path in &paths {
+                        let mut sequence = sequence.clone();
+                        sequence.push(path.iter().map(|&dir| char::from(dir)).collect());
+                        entry.2.push(sequence);
+                    }
+                }
+            }
+        }
+        states = next;
+    }
+    let best = states.values().map(|state| state.1).min();
+    states.into_iter()
+        .filter(|&(_, (_, cost, _))| Some(cost) == best)
+        .flat_map(|(_, (_, _, sequences))| sequences)
+        .collect()
+}
+
+/// The instructions for the bathroom keypad, one line per key.
+pub struct Instructions(Vec<Vec<Direction>>);
+
+impl Instructions {
+    /// Get the PIN these instructions give on a given keypad.
+    fn pin<K: Keypad>(&self, keypad: &K) -> String {
+        self.0
+            .iter()
+            .scan(keypad.start_pos(), |pos, line| {
+                *pos = pos.follow(line);
+                Some(pos.label())
+            })
+            .collect()
+    }
+}
+
+impl Solution for Instructions {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        input.lines()
+            .enumerate()
+            .map(|(i, line)| parse_line(line).map_err(|e| e.at_line(i + 1)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Instructions)
+    }
+
+    fn part1(&self) -> Box<dyn fmt::Display> {
+        Box::new(self.pin(&NineDigitKeypad))
+    }
+
+    fn part2(&self) -> Box<dyn fmt::Display> {
+        Box::new(self.pin(&DiamondKeypad))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use super::*;
 
-    fn create_pos() -> KeypadPos<NineDigitKeypad> {
-        NineDigitKeypad::start_pos()
+    /// Split a PIN of single character labels into its labels.
+    fn labels(pin: &str) -> Vec<String> {
+        pin.chars().map(|c| c.to_string()).collect()
+    }
+
+    fn create_pos() -> KeypadPos<'static, NineDigitKeypad> {
+        NineDigitKeypad.start_pos()
     }
 
     #[test]
     fn parse_direction() {
-        assert_eq!(Direction::Up, Direction::from('U'));
-        assert_eq!(Direction::Down, Direction::from('D'));
-        assert_eq!(Direction::Left, Direction::from('L'));
-        assert_eq!(Direction::Right, Direction::from('R'));
+        assert_eq!(Ok(Direction::Up), Direction::try_from('U'));
+        assert_eq!(Ok(Direction::Down), Direction::try_from('D'));
+        assert_eq!(Ok(Direction::Left), Direction::try_from('L'));
+        assert_eq!(Ok(Direction::Right), Direction::try_from('R'));
     }
 
     #[test]
-    #[should_panic]
     fn parse_invalid_direction() {
-        Direction::from('H');
+        assert_eq!(Err(ParseError::unexpected_char(1, "one of 'U', 'D', 'L' or 'R'", 'H')),
+                   Direction::try_from('H'));
     }
 
     #[test]
     fn keypad_pos_defaults_to_5() {
         let pos = create_pos();
-        assert_eq!('5', pos.digit());
+        assert_eq!("5", pos.label());
     }
 
     #[test]
     fn diamond_keypad_start_at_5() {
-        let pos = DiamondKeypad::start_pos();
-        assert_eq!('5', pos.digit());
+        let pos = DiamondKeypad.start_pos();
+        assert_eq!("5", pos.label());
     }
 
     #[test]
     fn move_keypad_pos() {
         let pos = create_pos();
-        assert_eq!('2', pos.move_key(Direction::Up).digit());
-        assert_eq!('4', pos.move_key(Direction::Left).digit());
-        assert_eq!('6', pos.move_key(Direction::Right).digit());
-        assert_eq!('8', pos.move_key(Direction::Down).digit());
+        assert_eq!("2", pos.move_key(Direction::Up).label());
+        assert_eq!("4", pos.move_key(Direction::Left).label());
+        assert_eq!("6", pos.move_key(Direction::Right).label());
+        assert_eq!("8", pos.move_key(Direction::Down).label());
     }
 
     #[test]
     fn move_keypad_pos_saturates() {
         let pos = create_pos();
-        assert_eq!('2',
-                   pos.move_key(Direction::Up).move_key(Direction::Up).digit());
-        assert_eq!('7',
+        assert_eq!("2",
+                   pos.move_key(Direction::Up).move_key(Direction::Up).label());
+        assert_eq!("7",
                    pos.move_key(Direction::Down)
                        .move_key(Direction::Down)
                        .move_key(Direction::Left)
                        .move_key(Direction::Left)
-                       .digit());
-        assert_eq!('6',
-                   pos.move_key(Direction::Right).move_key(Direction::Right).digit());
-        assert_eq!('4',
+                       .label());
+        assert_eq!("6",
+                   pos.move_key(Direction::Right).move_key(Direction::Right).label());
+        assert_eq!("4",
                    pos.move_key(Direction::Left)
                        .move_key(Direction::Left)
                        .move_key(Direction::Left)
-                       .digit());
+                       .label());
     }
 
     #[test]
     fn move_diamond_saturates() {
-        let pos = DiamondKeypad::start_pos();
-        assert_eq!('5', pos.move_by("L").digit());
+        let pos = DiamondKeypad.start_pos();
+        assert_eq!("5", pos.move_by("L").unwrap().label());
     }
 
     #[test]
     fn move_by_direction_string() {
         let pos = create_pos();
-        assert_eq!('1', pos.move_by("ULL").digit());
+        assert_eq!("1", pos.move_by("ULL").unwrap().label());
+    }
+
+    #[test]
+    fn move_by_invalid_direction_string() {
+        let err = create_pos().move_by("ULX").err().unwrap();
+        assert_eq!(3, err.column());
     }
 
-    const INSTRUCTIONS: &'static str = "ULL
+    const INSTRUCTIONS: &str = "ULL
 RRDDD
 LURDL
 UUUUD";
 
     #[test]
     fn example_works() {
-        let pin = NineDigitKeypad::pin_from(INSTRUCTIONS);
-        assert_eq!("1985", pin);
+        let pin = NineDigitKeypad.pin_from(INSTRUCTIONS);
+        assert_eq!(Ok(labels("1985")), pin);
     }
 
     #[test]
     fn example_diamond_works() {
-        let pin = DiamondKeypad::pin_from(INSTRUCTIONS);
+        let pin = DiamondKeypad.pin_from(INSTRUCTIONS);
+        assert_eq!(Ok(labels("5DB3")), pin);
+    }
+
+    #[test]
+    fn grid_keypads_match_builtin_keypads() {
+        let square = GridKeypad::new("123\n456\n789", "5").unwrap();
+        assert_eq!(Ok(labels("1985")), square.pin_from(INSTRUCTIONS));
+        let diamond = GridKeypad::new("  1\n 234\n56789\n ABC\n  D", "5").unwrap();
+        assert_eq!(Ok(labels("5DB3")), diamond.pin_from(INSTRUCTIONS));
+    }
+
+    #[test]
+    fn grid_keypad_holes() {
+        let keypad = GridKeypad::new("1 2\n345", "4").unwrap();
+        assert_eq!("4", keypad.start_pos().label());
+        assert_eq!("4", keypad.start_pos().move_key(Direction::Up).label());
+        assert_eq!("2", keypad.start_pos().move_by("RU").unwrap().label());
+        assert_eq!("1", keypad.start_pos().move_by("LUR").unwrap().label());
+    }
+
+    #[test]
+    fn grid_keypad_without_start_key() {
+        assert_eq!(Err(ParseError::unexpected_end(3, "a key labelled \"5\"").at_line(2)),
+                   GridKeypad::new("123\n 4", "5"));
+        assert_eq!(Err(ParseError::unexpected_end(1, "a key labelled \" \"")),
+                   GridKeypad::new("", " "));
+        assert_eq!(Err(ParseError::unexpected_end(11, "a key labelled \"ENTER\"").at_line(2)),
+                   GridKeypad::from_cells("1|2|3\n |0 |CLEAR", "ENTER"));
+    }
+
+    #[test]
+    fn grid_keypad_multi_character_labels() {
+        let keypad = GridKeypad::from_cells("1|2|3\n4|5|6\n7|8|9\n|0|ENTER", "5").unwrap();
+        assert_eq!(Ok(vec!["0".to_string(), "ENTER".to_string(), "8".to_string()]),
+                   keypad.pin_from("DD\nR\nUL"));
+        assert_eq!("0", keypad.start_pos().move_by("DDDLL").unwrap().label());
+        assert_eq!(Some(vec!["RDD".to_string(), "UL".to_string()]),
+                   keypad.instructions_for(&["ENTER", "8"]));
+    }
+
+    #[test]
+    fn grid_keypad_wraps() {
+        let keypad = GridKeypad::new("123\n456\n789", "5").unwrap().with_edge(Edge::Wrap);
+        assert_eq!("8", keypad.start_pos().move_by("UU").unwrap().label());
+        assert_eq!("4", keypad.start_pos().move_by("RR").unwrap().label());
+        let trace = keypad.start_pos().trace("LL").unwrap();
+        assert_eq!(vec![Outcome::Moved, Outcome::Wrapped],
+                   trace.iter().map(|step| step.outcome).collect::<Vec<_>>());
+
+        // Holes on the far side are skipped, but holes inside the
+        // grid stop a move like an edge does.
+        let diamond = GridKeypad::new("  1\n 234\n56789\n ABC\n  D", "5")
+            .unwrap()
+            .with_edge(Edge::Wrap);
+        assert_eq!("9", diamond.start_pos().move_by("L").unwrap().label());
+        assert_eq!("1", diamond.start_pos().move_by("RRUU").unwrap().label());
+        assert_eq!("D", diamond.start_pos().move_by("RRUUU").unwrap().label());
+        assert_eq!("4", diamond.start_pos().move_by("RRRUR").unwrap().label());
+        assert_eq!(Outcome::Ignored, diamond.start_pos().step(Direction::Up).1);
+        let gappy = GridKeypad::new("1 2", "1").unwrap().with_edge(Edge::Wrap);
+        assert_eq!("1", gappy.start_pos().move_by("R").unwrap().label());
+        assert_eq!(Outcome::Ignored, gappy.start_pos().step(Direction::Right).1);
+        assert_eq!(Outcome::Wrapped, gappy.start_pos().step(Direction::Left).1);
+        assert_eq!("2", gappy.start_pos().move_by("L").unwrap().label());
+        assert_eq!(Outcome::Ignored, gappy.start_pos().step(Direction::Up).1);
+        let stacked = GridKeypad::new("1\n\n2", "1").unwrap().with_edge(Edge::Wrap);
+        assert_eq!("1", stacked.start_pos().move_by("D").unwrap().label());
+        assert_eq!(Outcome::Ignored, stacked.start_pos().step(Direction::Down).1);
+        assert_eq!("2", stacked.start_pos().move_by("U").unwrap().label());
+
+        assert_eq!(Some(vec!["UL".to_string(), "L".to_string()]),
+                   keypad.instructions_for(&["1", "3"]));
+    }
+
+    #[test]
+    fn grid_keypad_teleports() {
+        let keypad = GridKeypad::new("123\n456\n789", "5")
+            .unwrap()
+            .with_edge(Edge::Teleport("5".to_string()));
+        let trace = keypad.start_pos().trace("UUL").unwrap();
+        assert_eq!(vec![("2", Outcome::Moved), ("5", Outcome::Teleported), ("4", Outcome::Moved)],
+                   trace.iter().map(|step| (step.label(), step.outcome)).collect::<Vec<_>>());
+        let nowhere = GridKeypad::new("12", "1").unwrap().with_edge(Edge::Teleport("X".to_string()));
+        assert_eq!(Outcome::Ignored, nowhere.start_pos().step(Direction::Left).1);
+    }
+
+    #[test]
+    fn weighted_keys() {
+        let keypad = GridKeypad::new("123\n456\n789", "5").unwrap();
+        assert_eq!(Some(vec!["RD".to_string()]), keypad.instructions_for(&["9"]));
+        let keypad = keypad.with_weight("6", 5);
+        assert_eq!(Some(vec!["DR".to_string()]), keypad.instructions_for(&["9"]));
+        assert_eq!(vec![vec!["DR".to_string()]], keypad.all_instructions_for(&["9"]));
+        assert_eq!(Some(vec!["R".to_string()]), keypad.instructions_for(&["6"]));
+    }
+
+    #[test]
+    fn shortest_instructions_for_pin() {
+        assert_eq!(Some(vec!["UL".to_string(), "RRDD".to_string(), "L".to_string(), "U".to_string()]),
+                   NineDigitKeypad.instructions_for(&labels("1985")));
+        assert_eq!(Some(vec![String::new(), String::new()]),
+                   NineDigitKeypad.instructions_for(&["5", "5"]));
+        assert_eq!(Some(vec![]), NineDigitKeypad.instructions_for::<&str>(&[]));
+        assert_eq!(None, NineDigitKeypad.instructions_for(&["10"]));
+    }
+
+    #[test]
+    fn shortest_instructions_round_trip() {
+        let lines = DiamondKeypad.instructions_for(&labels("5DB3")).unwrap();
+        assert_eq!(Ok(labels("5DB3")), DiamondKeypad.pin_from(&lines.join("\n")));
+        assert_eq!(vec![0, 4, 1, 2], lines.iter().map(|l| l.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn all_shortest_instructions_for_pin() {
+        assert_eq!(vec![vec!["UL".to_string()], vec!["LU".to_string()]],
+                   NineDigitKeypad.all_instructions_for(&["1"]));
+        let all = NineDigitKeypad.all_instructions_for(&["1", "9"]);
+        assert_eq!(12, all.len());
+        for lines in all {
+            assert_eq!(Ok(labels("19")), NineDigitKeypad.pin_from(&lines.join("\n")));
+        }
+        assert!(NineDigitKeypad.all_instructions_for(&["0"]).is_empty());
+    }
+
+    #[test]
+    fn shortest_instructions_with_repeated_labels() {
+        // The nearest 'A' is further from the 'B'.
+        let keypad = GridKeypad::new("BxxAxSA", "S").unwrap();
+        assert_eq!(Some(vec!["LL".to_string(), "LLL".to_string()]),
+                   keypad.instructions_for(&["A", "B"]));
+    }
+
+    #[test]
+    fn trace_moves() {
+        let trace = create_pos().trace("ULL").unwrap();
+        assert_eq!(vec![(Direction::Up, "2", Outcome::Moved),
+                        (Direction::Left, "1", Outcome::Moved),
+                        (Direction::Left, "1", Outcome::Ignored)],
+                   trace.iter()
+                       .map(|step| (step.direction, step.label(), step.outcome))
+                       .collect::<Vec<_>>());
+        assert_eq!(Ok(vec![]), create_pos().trace(""));
+        assert_eq!(5, create_pos().trace("UDLRX").err().unwrap().column());
+    }
+
+    #[test]
+    fn trace_instructions() {
+        let traces = DiamondKeypad.trace_from(INSTRUCTIONS).unwrap();
+        assert_eq!(vec![3, 5, 5, 5], traces.iter().map(|t| t.len()).collect::<Vec<_>>());
+        let digits = traces.iter().map(|t| t.last().unwrap().label()).collect::<String>();
+        assert_eq!("5DB3", digits);
+        let ignored = traces[0].iter().filter(|step| step.outcome == Outcome::Ignored).count();
+        assert_eq!(3, ignored);
+        let err = DiamondKeypad.trace_from("UL\nRX").err().unwrap();
+        assert_eq!((2, 2), (err.line(), err.column()));
+    }
+
+    #[test]
+    fn crlf_instructions() {
+        assert_eq!(Ok(labels("1985")),
+                   NineDigitKeypad.pin_from(&INSTRUCTIONS.replace("\n", "\r\n")));
+        assert_eq!(Ok(labels("1")), NineDigitKeypad.pin_from("UL\n"));
+        assert_eq!(Ok(labels("1")), NineDigitKeypad.pin_from("UL\r\n"));
+        assert_eq!(Ok(labels("11")), NineDigitKeypad.pin_from("UL\r\n\r\n"));
+        let input = format!("{}\r\n", INSTRUCTIONS.replace("\n", "\r\n"));
+        assert_eq!(Ok(labels("1985")), NineDigitKeypad.pin_from(&input));
+        assert_eq!(NineDigitKeypad.pin_from_reader(input.as_bytes()).unwrap(),
+                   NineDigitKeypad.pin_from(&input).unwrap());
+        assert_eq!(4, NineDigitKeypad.trace_from(&input).unwrap().len());
+    }
+
+    #[test]
+    fn read_pin_from_stream() {
+        let input = "ULL\r\n\r\nRRDDD\n  \nLURDL\r\nUUUUD\r\n";
+        let pin = NineDigitKeypad.pin_from_reader(input.as_bytes()).unwrap();
+        assert_eq!(labels("1985"), pin);
+        let pin = DiamondKeypad.pin_reader(input.as_bytes())
+            .map(|key| key.unwrap())
+            .collect::<String>();
         assert_eq!("5DB3", pin);
     }
 
+    #[test]
+    fn read_pin_reports_errors() {
+        let input = "ULL\r\n\r\nRRDxD\r\n";
+        match NineDigitKeypad.pin_from_reader(input.as_bytes()) {
+            Err(ReadError::Parse(err)) => assert_eq!((3, 4), (err.line(), err.column())),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        match NineDigitKeypad.pin_from_reader(&b"UL\n\xff\n"[..]) {
+            Err(ReadError::Io(_)) => (),
+            other => panic!("expected an IO error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_instructions_report_line() {
+        let err = NineDigitKeypad.pin_from("ULL\nRRDDD\nLURxL").err().unwrap();
+        assert_eq!(3, err.line());
+        assert_eq!(4, err.column());
+    }
+
+    #[test]
+    fn solve_example() {
+        let instructions = Instructions::parse(INSTRUCTIONS).unwrap();
+        assert_eq!("1985", instructions.part1().to_string());
+        assert_eq!("5DB3", instructions.part2().to_string());
+    }
+
 }
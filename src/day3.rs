@@ -4,64 +4,454 @@
 //! triangle is considered 'valid' if all sides are shorter than the
 //! sum of the other two sides.
 
-/// Counts the valid triangles in a list of sides.
-fn count_valid_triangles(numbers: Vec<i32>) -> usize {
-    numbers.chunks(3)
-        .filter(|sides| is_triangular(sides))
-        .count()
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::{ParseError, Solution};
+use error::parse_number;
+
+/// A way of grouping a list of numbers into the sides of shapes.
+///
+/// Numbers are grouped in blocks. Only complete groups are returned,
+/// so numbers at the end of the list which don't fill a group are
+/// left over.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Grouping {
+    /// Each row of three numbers makes a group.
+    Rows,
+    /// Each column of a table with three columns makes groups of
+    /// three, read down the column.
+    Columns,
+    /// Blocks of `stride * size` numbers each make `stride` groups of
+    /// `size` numbers. Each group starts at a different offset within
+    /// the block, and takes every `stride`th number from there. Both
+    /// must be positive, and `stride * size` must fit in a `usize`, for
+    /// any groups to be made; tables reject groupings which don't.
+    Strided { stride: usize, size: usize },
+    /// Each block of three rows in a table with three columns makes
+    /// three groups, read along the diagonals. The diagonals wrap
+    /// around, so each number is in exactly one group.
+    Diagonal,
 }
 
-/// Get the whitespace-separated numbers from a string.
-fn get_numbers(triangles: &str) -> Vec<i32> {
-    triangles.split_whitespace()
-        .map(|s| i32::from_str_radix(s, 10).unwrap())
-        .collect()
+/// A table of numbers, with the same number of numbers on each row.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Table {
+    width: usize,
+    numbers: Vec<i32>,
+    /// The line each row was read from.
+    lines: Vec<usize>,
 }
 
-/// Returns true if the given string represents a valid triangle.
-pub fn is_triangular(sides: &[i32]) -> bool {
-    let total: i32 = sides.iter().sum();
+impl Table {
+    /// Parse a table of whitespace-separated numbers, with `width`
+    /// numbers on each row. Blank lines are skipped. Tables must have
+    /// at least one column, so a `width` of zero is an error.
+    pub fn parse(input: &str, width: usize) -> Result<Self, ParseError> {
+        if width == 0 {
+            return Err(ParseError::unexpected(1, "a table width of at least 1", "0"));
+        }
+        let mut table = Table {
+            width,
+            numbers: Vec::new(),
+            lines: Vec::new(),
+        };
+        for (index, line) in input.lines().enumerate() {
+            table.parse_row(line).map_err(|e| e.at_line(index + 1))?;
+            if table.numbers.len() > table.lines.len() * width {
+                table.lines.push(index + 1);
+            }
+        }
+        Ok(table)
+    }
 
-    for i in 0..3 {
-        let len = sides[i];
-        if (total - len) <= len {
-            return false;
+    /// Parse a single line, adding its numbers to the table.
+    fn parse_row(&mut self, line: &str) -> Result<(), ParseError> {
+        let mut found = 0;
+        for (column, token) in tokens(line) {
+            if found == self.width {
+                return Err(ParseError::unexpected(column, "end of line", token));
+            }
+            self.numbers.push(parse_number(token, column)?);
+            found += 1;
+        }
+        if found > 0 && found < self.width {
+            return Err(ParseError::unexpected_end(line.trim_end().chars().count() + 1, "a number"));
         }
+        Ok(())
     }
-    true
-}
 
-/// Counts the valid triangles, taking sides from each line.
-pub fn count_by_line(triangles: &str) -> usize {
-    let numbers = get_numbers(triangles);
-    count_valid_triangles(numbers)
-}
-
-/// Counts the valid triangles in a given column.
-fn count_at_offset<'a, T>(numbers: T, offset: usize) -> usize
-    where T: IntoIterator<Item = &'a (usize, i32)>
-{
-    let numbers = numbers.into_iter()
-        .filter_map(|&(i, side)| {
-            if (i + offset) % 3 == 0 {
-                Some(side)
+    /// Get the number of numbers on each row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get all of the numbers in the table, row by row.
+    pub fn numbers(&self) -> &[i32] {
+        &self.numbers
+    }
+
+    /// Get the rows of the table.
+    pub fn rows(&self) -> impl Iterator<Item = &[i32]> {
+        self.numbers.chunks(self.width)
+    }
+
+    /// Split the table's numbers up into groups.
+    ///
+    /// Unlike `Grouping::groups` every number must be used, so tables
+    /// which don't fill the last block are rejected.
+    pub fn groups(&self,
+                  grouping: Grouping)
+                  -> Result<impl Iterator<Item = Vec<i32>> + '_, ParseError> {
+        self.check_blocks(grouping)?;
+        Ok(grouping.groups(&self.numbers))
+    }
+
+    /// Check that the grouping makes groups, and that the table fills
+    /// a whole number of its blocks.
+    fn check_blocks(&self, grouping: Grouping) -> Result<(), ParseError> {
+        let (block_len, _, _) = match grouping.shape() {
+            Some(shape) => shape,
+            None => {
+                return Err(ParseError::unexpected(1,
+                                                  "a grouping with a positive block size",
+                                                  format!("{:?}", grouping)))
+            }
+        };
+        let len = self.numbers.len();
+        if !len.is_multiple_of(block_len) {
+            let missing = block_len - len % block_len;
+            let expected = if block_len % self.width == 0 {
+                format!("{} more rows to complete a block of {}",
+                        missing / self.width,
+                        block_len / self.width)
             } else {
-                None
+                format!("{} more numbers to complete a block of {}", missing, block_len)
+            };
+            let line = self.lines.last().map_or(1, |line| line + 1);
+            return Err(ParseError::unexpected_end(1, expected).at_line(line));
+        }
+        Ok(())
+    }
+
+    /// Counts the valid triangles, with sides grouped in a given way.
+    pub fn count_by(&self, grouping: Grouping) -> Result<usize, ParseError> {
+        Ok(self.groups(grouping)?
+            .filter(|sides| is_triangular(sides))
+            .count())
+    }
+
+    /// Get statistics about the triangles, with sides grouped in a
+    /// given way.
+    pub fn stats(&self, grouping: Grouping) -> Result<Stats, ParseError> {
+        let mut stats = Stats::default();
+        for sides in self.groups(grouping)? {
+            stats.add(&sides);
+        }
+        Ok(stats)
+    }
+}
+
+/// Get the whitespace-separated tokens in a line, along with the
+/// column each one starts at.
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split(|c: char| c.is_whitespace())
+        .scan(1, |column, token| {
+            let start = *column;
+            *column += token.chars().count() + 1;
+            Some((start, token))
+        })
+        .filter(|&(_, token)| !token.is_empty())
+}
+
+/// The reason a list of sides can't make a polygon.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Violation {
+    /// Fewer than three sides were given.
+    TooFewSides(usize),
+    /// The side at index `side` is zero or negative.
+    NonPositive { side: usize },
+    /// The side at index `side` isn't shorter than all of the other
+    /// sides put together. The `excess` is how much longer it is than
+    /// them, and is zero if they are the same length.
+    TooLong {
+        side: usize,
+        length: i64,
+        excess: i64,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Violation::TooFewSides(count) => {
+                write!(f, "a polygon needs at least 3 sides, found {}", count)
             }
+            Violation::NonPositive { side } => write!(f, "side {} isn't a positive length", side),
+            Violation::TooLong { side, length, excess } => {
+                write!(f,
+                       "side {} has length {}, which is {} more than the other sides combined",
+                       side,
+                       length,
+                       excess)
+            }
+        }
+    }
+}
+
+/// Check if the given sides can make a polygon.
+///
+/// Each side must be positive, and shorter than the sum of all the
+/// others. Given positive sides it is enough to check the longest
+/// side, so that is the one reported if the check fails.
+pub fn check_polygon(sides: &[i32]) -> Result<(), Violation> {
+    if sides.len() < 3 {
+        return Err(Violation::TooFewSides(sides.len()));
+    }
+    if let Some(side) = sides.iter().position(|&len| len <= 0) {
+        return Err(Violation::NonPositive { side });
+    }
+    let total: i64 = sides.iter().map(|&len| i64::from(len)).sum();
+    let mut side = 0;
+    for (i, &len) in sides.iter().enumerate() {
+        if len > sides[side] {
+            side = i;
+        }
+    }
+    let length = i64::from(sides[side]);
+    let others = total - length;
+    if length < others {
+        Ok(())
+    } else {
+        Err(Violation::TooLong {
+            side,
+            length,
+            excess: length - others,
         })
-        .collect::<Vec<_>>();
-    count_valid_triangles(numbers)
+    }
+}
+
+/// Classification of a triangle by the lengths of its sides. Each
+/// triangle is in exactly one class, so equilateral triangles are not
+/// counted as isosceles.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SideClass {
+    /// All three sides are the same length.
+    Equilateral,
+    /// Exactly two sides are the same length.
+    Isosceles,
+    /// All three sides are different lengths.
+    Scalene,
+}
+
+/// Classification of a triangle by its largest angle.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum AngleClass {
+    /// All angles are less than a right angle.
+    Acute,
+    /// One angle is a right angle.
+    Right,
+    /// One angle is more than a right angle.
+    Obtuse,
+}
+
+/// A valid triangle.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Triangle {
+    /// The sides, shortest first.
+    sides: [i32; 3],
+}
+
+impl Triangle {
+    /// Create a triangle from the lengths of its sides, if they make
+    /// a valid one.
+    pub fn new(a: i32, b: i32, c: i32) -> Result<Self, Violation> {
+        let mut sides = [a, b, c];
+        check_polygon(&sides)?;
+        sides.sort();
+        Ok(Triangle { sides })
+    }
+
+    /// Get the sides of the triangle, shortest first.
+    pub fn sides(&self) -> [i32; 3] {
+        self.sides
+    }
+
+    /// Classify the triangle by the lengths of its sides.
+    pub fn side_class(&self) -> SideClass {
+        let [a, b, c] = self.sides;
+        if a == c {
+            SideClass::Equilateral
+        } else if a == b || b == c {
+            SideClass::Isosceles
+        } else {
+            SideClass::Scalene
+        }
+    }
+
+    /// Classify the triangle by its largest angle, which is opposite
+    /// the longest side.
+    pub fn angle_class(&self) -> AngleClass {
+        let [a, b, c] = self.sides;
+        let square = |x: i32| i64::from(x) * i64::from(x);
+        match square(c).cmp(&(square(a) + square(b))) {
+            Ordering::Less => AngleClass::Acute,
+            Ordering::Equal => AngleClass::Right,
+            Ordering::Greater => AngleClass::Obtuse,
+        }
+    }
+
+    /// Get the perimeter of the triangle.
+    pub fn perimeter(&self) -> i64 {
+        self.sides.iter().map(|&side| i64::from(side)).sum()
+    }
+
+    /// Get the area of the triangle, using Heron's formula.
+    pub fn area(&self) -> f64 {
+        let [a, b, c] = self.sides;
+        let (a, b, c) = (f64::from(a), f64::from(b), f64::from(c));
+        // Heron's formula rearranged to avoid halving the perimeter.
+        ((a + b + c) * (b + c - a) * (a + c - b) * (a + b - c)).sqrt() / 4.0
+    }
+}
+
+/// Statistics about the triangles in an input.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Stats {
+    /// The number of valid triangles.
+    pub valid: usize,
+    /// The number of groups of sides which aren't valid triangles.
+    pub invalid: usize,
+    pub equilateral: usize,
+    pub isosceles: usize,
+    pub scalene: usize,
+    pub acute: usize,
+    pub right: usize,
+    pub obtuse: usize,
+    /// The first of the triangles with the largest area.
+    pub largest: Option<Triangle>,
+}
+
+impl Stats {
+    /// Add a group of sides to the statistics.
+    fn add(&mut self, sides: &[i32]) {
+        let triangle = match *sides {
+            [a, b, c] => Triangle::new(a, b, c).ok(),
+            _ => None,
+        };
+        let triangle = match triangle {
+            Some(triangle) => triangle,
+            None => {
+                self.invalid += 1;
+                return;
+            }
+        };
+        self.valid += 1;
+        match triangle.side_class() {
+            SideClass::Equilateral => self.equilateral += 1,
+            SideClass::Isosceles => self.isosceles += 1,
+            SideClass::Scalene => self.scalene += 1,
+        }
+        match triangle.angle_class() {
+            AngleClass::Acute => self.acute += 1,
+            AngleClass::Right => self.right += 1,
+            AngleClass::Obtuse => self.obtuse += 1,
+        }
+        if self.largest.is_none_or(|largest| triangle.area() > largest.area()) {
+            self.largest = Some(triangle);
+        }
+    }
+
+    /// Get the largest area of any of the triangles.
+    pub fn largest_area(&self) -> Option<f64> {
+        self.largest.map(|triangle| triangle.area())
+    }
+}
+
+/// Get statistics about the triangles, with sides grouped in a given
+/// way.
+pub fn stats(triangles: &str, grouping: Grouping) -> Result<Stats, ParseError> {
+    Table::parse(triangles, 3)?.stats(grouping)
+}
+
+/// Returns true if the given sides make a valid triangle.
+pub fn is_triangular(sides: &[i32]) -> bool {
+    sides.len() == 3 && check_polygon(sides).is_ok()
+}
+
+impl Grouping {
+    /// Get the number of numbers in each block, the number of groups
+    /// made from each block, and the number of numbers in each group.
+    /// Returns `None` if the grouping can't make any groups, because
+    /// its blocks are empty or too large to index.
+    fn shape(self) -> Option<(usize, usize, usize)> {
+        match self {
+            Grouping::Rows => Some((3, 1, 3)),
+            Grouping::Columns | Grouping::Diagonal => Some((9, 3, 3)),
+            Grouping::Strided { stride, size } => {
+                stride.checked_mul(size)
+                    .filter(|&block_len| block_len > 0)
+                    .map(|block_len| (block_len, stride, size))
+            }
+        }
+    }
+
+    /// Get the index within a block of the `nth` number in a group.
+    fn index(self, group: usize, nth: usize) -> usize {
+        match self {
+            Grouping::Rows => nth,
+            Grouping::Columns => group + nth * 3,
+            Grouping::Strided { stride, .. } => group + nth * stride,
+            Grouping::Diagonal => nth * 3 + (group + nth) % 3,
+        }
+    }
+
+    /// Split a list of numbers up into groups.
+    pub fn groups<'a>(self, numbers: &'a [i32]) -> impl Iterator<Item = Vec<i32>> + 'a {
+        let (block_len, count, size) = self.shape().unwrap_or((1, 0, 0));
+        numbers.chunks(block_len).flat_map(move |block| {
+            (0..count).filter_map(move |group| {
+                (0..size)
+                    .map(|nth| block.get(self.index(group, nth)).cloned())
+                    .collect::<Option<Vec<_>>>()
+            })
+        })
+    }
+}
+
+/// Counts the valid triangles, with sides grouped in a given way.
+pub fn count_by(triangles: &str, grouping: Grouping) -> Result<usize, ParseError> {
+    Table::parse(triangles, 3)?.count_by(grouping)
+}
+
+/// Counts the valid triangles, taking sides from each line.
+pub fn count_by_line(triangles: &str) -> Result<usize, ParseError> {
+    count_by(triangles, Grouping::Rows)
 }
 
 /// Counts the valid triangles, taking sides from each column.
-pub fn count_by_column(triangles: &str) -> usize {
-    let numbers = get_numbers(triangles)
-        .into_iter()
-        .enumerate()
-        .collect::<Vec<_>>();
-    (0..3)
-        .map(|i| count_at_offset(&numbers, i))
-        .sum()
+pub fn count_by_column(triangles: &str) -> Result<usize, ParseError> {
+    count_by(triangles, Grouping::Columns)
+}
+
+/// The triangle specifications from the design department.
+pub struct Triangles(Table);
+
+impl Solution for Triangles {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Table::parse(input, 3).map(Triangles)
+    }
+
+    fn part1(&self) -> Box<dyn fmt::Display> {
+        Box::new(self.0.count_by(Grouping::Rows).expect("rows are checked when parsed"))
+    }
+
+    fn part2(&self) -> Box<dyn fmt::Display> {
+        match self.0.count_by(Grouping::Columns) {
+            Ok(count) => Box::new(count),
+            Err(e) => Box::new(e),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -77,17 +467,193 @@ mod test {
     #[test]
     fn is_triangular_with_invalid_triangles() {
         assert!(!is_triangular(&[5, 10, 25]));
+        assert!(!is_triangular(&[5, 10]));
+        assert!(!is_triangular(&[]));
+        assert!(!is_triangular(&[2, 2, 2, 2]));
+    }
+
+    #[test]
+    fn check_polygons() {
+        assert_eq!(Ok(()), check_polygon(&[2, 1, 2]));
+        assert_eq!(Ok(()), check_polygon(&[1, 1, 1, 2]));
+        assert_eq!(Err(Violation::TooLong {
+                       side: 2,
+                       length: 25,
+                       excess: 10,
+                   }),
+                   check_polygon(&[5, 10, 25]));
+        assert_eq!(Err(Violation::TooLong {
+                       side: 3,
+                       length: 3,
+                       excess: 0,
+                   }),
+                   check_polygon(&[1, 1, 1, 3]));
+        assert_eq!(Err(Violation::TooFewSides(2)), check_polygon(&[3, 4]));
+        assert_eq!(Err(Violation::NonPositive { side: 0 }), check_polygon(&[-1, 5, 5, 5]));
+        assert_eq!(Err(Violation::NonPositive { side: 0 }), check_polygon(&[0, 5, 5, 5]));
+        assert_eq!(Err(Violation::NonPositive { side: 2 }), check_polygon(&[5, 5, 0]));
+        assert_eq!(Ok(()), check_polygon(&[i32::MAX, i32::MAX, i32::MAX]));
+    }
+
+    #[test]
+    fn display_violations() {
+        assert_eq!("side 2 has length 25, which is 10 more than the other sides combined",
+                   check_polygon(&[5, 10, 25]).unwrap_err().to_string());
+        assert_eq!("a polygon needs at least 3 sides, found 1",
+                   Violation::TooFewSides(1).to_string());
+        assert_eq!("side 0 isn't a positive length",
+                   check_polygon(&[-1, 5, 5]).unwrap_err().to_string());
     }
 
     #[test]
     fn vertical_example() {
-        const TRIANGLES: &'static str = "101 301 501
+        const TRIANGLES: &str = "101 301 501
 102 302 502
 103 303 503
 201 401 601
 202 402 \
                                          602
 203 403 603";
-        assert_eq!(6, count_by_column(TRIANGLES));
+        assert_eq!(Ok(6), count_by_column(TRIANGLES));
+    }
+
+    #[test]
+    fn group_numbers() {
+        let numbers = (1..=10).collect::<Vec<_>>();
+        let groups = |grouping: Grouping| grouping.groups(&numbers).collect::<Vec<_>>();
+        assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]], groups(Grouping::Rows));
+        assert_eq!(vec![vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 9]], groups(Grouping::Columns));
+        assert_eq!(vec![vec![1, 5, 9], vec![2, 6, 7], vec![3, 4, 8]], groups(Grouping::Diagonal));
+        assert_eq!(vec![vec![1, 3, 5, 7], vec![2, 4, 6, 8]],
+                   groups(Grouping::Strided { stride: 2, size: 4 }));
+        assert_eq!(groups(Grouping::Rows), groups(Grouping::Strided { stride: 1, size: 3 }));
+        assert_eq!(groups(Grouping::Columns), groups(Grouping::Strided { stride: 3, size: 3 }));
+    }
+
+    #[test]
+    fn group_incomplete_blocks() {
+        let numbers = (1..=14).collect::<Vec<_>>();
+        assert_eq!(vec![vec![10, 11, 12]],
+                   Grouping::Rows.groups(&numbers[9..]).collect::<Vec<_>>());
+        assert_eq!(6, Grouping::Strided { stride: 3, size: 2 }.groups(&numbers).count());
+        assert_eq!(3, Grouping::Columns.groups(&numbers).count());
+        assert_eq!(0, Grouping::Strided { stride: 0, size: 3 }.groups(&numbers).count());
+        let huge = Grouping::Strided {
+            stride: usize::MAX,
+            size: 2,
+        };
+        assert_eq!(0, huge.groups(&numbers).count());
+        let table = Table::parse("1 2 3\n4 5 6", 3).unwrap();
+        let expected = "a grouping with a positive block size";
+        assert_eq!(Err(ParseError::unexpected(1, expected, format!("{:?}", huge))),
+                   table.count_by(huge));
+        for &grouping in &[Grouping::Strided { stride: 0, size: 3 },
+                           Grouping::Strided { stride: 3, size: 0 }] {
+            assert_eq!(Err(ParseError::unexpected(1, expected, format!("{:?}", grouping))),
+                       table.count_by(grouping));
+        }
+        assert!(table.count_by(Grouping::Strided { stride: 100, size: 2 }).is_err());
+    }
+
+    #[test]
+    fn count_diagonals() {
+        const TRIANGLES: &str = "3 1 1\n1 4 1\n1 1 5";
+        assert_eq!(Ok(3), count_by(TRIANGLES, Grouping::Diagonal));
+        assert_eq!(Ok(0), count_by(TRIANGLES, Grouping::Rows));
+        assert_eq!(Ok(0), count_by(TRIANGLES, Grouping::Columns));
+    }
+
+    #[test]
+    fn classify_triangles() {
+        let triangle = Triangle::new(5, 3, 4).unwrap();
+        assert_eq!([3, 4, 5], triangle.sides());
+        assert_eq!(SideClass::Scalene, triangle.side_class());
+        assert_eq!(AngleClass::Right, triangle.angle_class());
+        assert_eq!(12, triangle.perimeter());
+        assert_eq!(6.0, triangle.area());
+
+        let triangle = Triangle::new(2, 2, 2).unwrap();
+        assert_eq!(SideClass::Equilateral, triangle.side_class());
+        assert_eq!(AngleClass::Acute, triangle.angle_class());
+        assert!((triangle.area() - 3f64.sqrt()).abs() < 1e-9);
+
+        let triangle = Triangle::new(5, 6, 5).unwrap();
+        assert_eq!(SideClass::Isosceles, triangle.side_class());
+        assert_eq!(AngleClass::Acute, triangle.angle_class());
+        assert_eq!(12.0, triangle.area());
+
+        assert_eq!(AngleClass::Obtuse, Triangle::new(2, 3, 2).unwrap().angle_class());
+        assert_eq!(Err(Violation::TooLong {
+                       side: 2,
+                       length: 25,
+                       excess: 10,
+                   }),
+                   Triangle::new(5, 10, 25));
+        assert_eq!(Err(Violation::NonPositive { side: 0 }), Triangle::new(-1, 5, 5));
+        assert_eq!(Err(Violation::NonPositive { side: 1 }), Triangle::new(5, 0, 5));
+    }
+
+    #[test]
+    fn triangle_stats() {
+        let stats = stats("3 4 5\n2 2 2\n5 6 5\n5 10 25\n2 3 2", Grouping::Rows).unwrap();
+        assert_eq!(4, stats.valid);
+        assert_eq!(1, stats.invalid);
+        assert_eq!((1, 2, 1), (stats.equilateral, stats.isosceles, stats.scalene));
+        assert_eq!((2, 1, 1), (stats.acute, stats.right, stats.obtuse));
+        assert_eq!(Triangle::new(5, 5, 6).ok(), stats.largest);
+        assert_eq!(Some(12.0), stats.largest_area());
+        assert_eq!(Ok(Stats::default()), super::stats("", Grouping::Columns));
+    }
+
+    #[test]
+    fn parse_tables() {
+        let table = Table::parse("  1  2  3\r\n\n 4 5\t6\n", 3).unwrap();
+        assert_eq!(3, table.width());
+        assert_eq!(&[1, 2, 3, 4, 5, 6], table.numbers());
+        assert_eq!(vec![&[1, 2, 3][..], &[4, 5, 6][..]], table.rows().collect::<Vec<_>>());
+        assert_eq!(Ok(Table::parse("", 3).unwrap()), Table::parse(" \n\n", 3));
+    }
+
+    #[test]
+    fn parse_ragged_tables() {
+        assert_eq!(Err(ParseError::unexpected_end(5, "a number").at_line(2)),
+                   Table::parse("1 2 3\n4  5\n6 7 8", 3));
+        assert_eq!(Err(ParseError::unexpected(7, "end of line", "4").at_line(1)),
+                   Table::parse("1 2 3 4\n5 6 7", 3));
+        assert_eq!(Err(ParseError::unexpected_char(3, "a number", 'x').at_line(3)),
+                   Table::parse("1 2 3\n\n1 x 3", 3));
+        assert_eq!(Err(ParseError::unexpected_char(4, "a digit", 'e').at_line(1)),
+                   Table::parse("1 2e3 3", 3));
+        assert_eq!(Err(ParseError::unexpected_char(5, "a number", 'x').at_line(1)),
+                   Table::parse("1\u{3000}2 x", 3));
+        assert_eq!(Err(ParseError::unexpected(1, "a table width of at least 1", "0")),
+                   Table::parse("1 2 3", 0));
+        assert_eq!(Err(ParseError::unexpected(1, "a table width of at least 1", "0")),
+                   Table::parse("", 0));
+    }
+
+    #[test]
+    fn group_incomplete_tables() {
+        let table = Table::parse("3 4 5\n\n5 6 5\n", 3).unwrap();
+        assert_eq!(Ok(2), table.count_by(Grouping::Rows));
+        assert_eq!(Err(ParseError::unexpected_end(1, "1 more rows to complete a block of 3")
+                       .at_line(4)),
+                   table.count_by(Grouping::Columns));
+        assert_eq!(Err(ParseError::unexpected_end(1, "2 more numbers to complete a block of 4")
+                       .at_line(4)),
+                   table.count_by(Grouping::Strided { stride: 2, size: 2 }));
+        assert!(Triangles::parse("3 4 5\n5 6").is_err());
+        let triangles = Triangles::parse("3 4 5\n5 6 5").unwrap();
+        assert_eq!("2", triangles.part1().to_string());
+        assert_eq!("line 3, column 1: expected 1 more rows to complete a block of 3, found end of \
+                    input",
+                   triangles.part2().to_string());
+    }
+
+    #[test]
+    fn solve_triangles() {
+        let triangles = Triangles::parse("5 10 25\n3 4 5\n10 10 10").unwrap();
+        assert_eq!("2", triangles.part1().to_string());
+        assert_eq!("1", triangles.part2().to_string());
     }
 }
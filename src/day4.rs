@@ -3,70 +3,803 @@
 //! Identifiying room numbers
 
 extern crate onig;
+#[cfg(feature = "serde")]
+extern crate serde;
 
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
 
-use self::onig::*;
+use self::onig::Regex;
+#[cfg(feature = "serde")]
+use self::serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use super::{ParseError, Solution};
+use error::{column_at, expect_str, read_number};
+
+/// The number of letters in a checksum.
+const CHECKSUM_LEN: usize = 5;
 
 /// Room id, with checksum.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Room<'a> {
     pub name: &'a str,
     pub sector: u32,
     pub checksum: &'a str,
 }
 
-impl<'a> From<&'a str> for Room<'a> {
+impl<'a> TryFrom<&'a str> for Room<'a> {
+    type Error = ParseError;
+
     /// Parse a room from a string.
-    fn from(s: &'a str) -> Room<'a> {
-        let pattern = Regex::new("([a-z\\-]+)-([0-9]+)\\[([a-z]+)\\]").unwrap();
-        let captures = pattern.captures(s).expect("invalid room format");
+    ///
+    /// The name is made of lowercase letters and dashes, and is
+    /// separated from the sector ID by a dash. The checksum is made of
+    /// lowercase letters, in square brackets.
+    fn try_from(s: &'a str) -> Result<Room<'a>, ParseError> {
+        Room::parse_with(s, &ChecksumPolicy::default())
+    }
+}
+
+/// An owned room id, with checksum.
+///
+/// This is the owned counterpart of `Room`, for keeping rooms after
+/// the input they were parsed from has gone.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoomBuf {
+    pub name: String,
+    pub sector: u32,
+    pub checksum: String,
+}
+
+impl RoomBuf {
+    /// Borrow this room as a `Room`.
+    pub fn as_room(&self) -> Room<'_> {
         Room {
-            name: captures.at(1).unwrap(),
-            sector: captures.at(2).unwrap().parse::<u32>().unwrap(),
-            checksum: captures.at(3).unwrap(),
+            name: &self.name,
+            sector: self.sector,
+            checksum: &self.checksum,
         }
     }
 }
 
-impl<'a> Room<'a> {
-    /// Test if a room's checksum is valid.
-    pub fn is_valid(&self) -> bool {
-        let mut char_counts = self.name
-            .replace("-", "")
-            .chars()
-            .fold(HashMap::new(), |mut counts, c| {
-                {
-                    let counter = counts.entry(c).or_insert(0);
-                    *counter += 1;
+impl<'a> From<Room<'a>> for RoomBuf {
+    fn from(room: Room<'a>) -> Self {
+        RoomBuf {
+            name: room.name.to_string(),
+            sector: room.sector,
+            checksum: room.checksum.to_string(),
+        }
+    }
+}
+
+impl<'a> From<&'a RoomBuf> for Room<'a> {
+    fn from(room: &'a RoomBuf) -> Self {
+        room.as_room()
+    }
+}
+
+impl FromStr for RoomBuf {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Room::try_from(s).map(RoomBuf::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> Serialize for Room<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut room = serializer.serialize_struct("Room", 5)?;
+        room.serialize_field("name", self.name)?;
+        room.serialize_field("sector", &self.sector)?;
+        room.serialize_field("checksum", self.checksum)?;
+        room.serialize_field("valid", &self.is_valid())?;
+        room.serialize_field("decrypted", &self.decrypt_name())?;
+        room.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for RoomBuf {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_room().serialize(serializer)
+    }
+}
+
+/// How letters which appear the same number of times are ordered in
+/// a checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Earlier letters in the alphabet come first.
+    Alphabetical,
+    /// Later letters in the alphabet come first.
+    Reverse,
+    /// Letters which appear earlier in the name come first.
+    FirstOccurrence,
+}
+
+/// The letters which room names and checksums are made of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Alphabet {
+    /// The lowercase ASCII letters, `a` to `z`.
+    Ascii,
+    /// Any Unicode letter, ordered by code point.
+    Unicode,
+    /// A list of letters, in alphabetical order.
+    Custom(Vec<char>),
+}
+
+impl Alphabet {
+    /// Create an alphabet from a string of letters, in alphabetical
+    /// order. Dashes, digits and brackets can't be letters, as they
+    /// separate the parts of a room.
+    ///
+    /// # Panics
+    ///
+    /// If there are no letters, or any of them is a dash, digit or
+    /// bracket.
+    pub fn custom(letters: &str) -> Self {
+        assert!(!letters.is_empty(), "alphabets must have at least one letter");
+        assert!(!letters.contains(|c: char| c == '-' || c == '[' || c == ']' || c.is_ascii_digit()),
+                "alphabets can't contain dashes, digits or brackets");
+        Alphabet::Custom(letters.chars().collect())
+    }
+
+    /// Get the position of a letter in the alphabet, if it is in it.
+    fn rank(&self, c: char) -> Option<usize> {
+        match *self {
+            Alphabet::Ascii if c.is_ascii_lowercase() => Some((c as u8 - b'a') as usize),
+            Alphabet::Ascii => None,
+            Alphabet::Unicode if c.is_alphabetic() => Some(c as usize),
+            Alphabet::Unicode => None,
+            Alphabet::Custom(ref letters) => letters.iter().position(|&letter| letter == c),
+        }
+    }
+
+    /// Check if a character is in the alphabet.
+    fn contains(&self, c: char) -> bool {
+        self.rank(c).is_some()
+    }
+
+    /// Get the number of letters in the alphabet.
+    fn len(&self) -> usize {
+        static UNICODE_LETTERS: OnceLock<usize> = OnceLock::new();
+        match *self {
+            Alphabet::Ascii => 26,
+            Alphabet::Unicode => {
+                *UNICODE_LETTERS.get_or_init(|| unicode_letters('\0', true).count())
+            }
+            Alphabet::Custom(ref letters) => letters.len(),
+        }
+    }
+
+    /// Shift each letter in a name by `shift` places, forwards or
+    /// backwards, wrapping around at the ends of the alphabet. Other
+    /// characters are left alone.
+    fn shift(&self, name: &str, shift: u32, forwards: bool) -> String {
+        let len = self.len();
+        let shift = if len == 0 { 0 } else { shift as usize % len };
+        let offset = if forwards { shift } else { len - shift };
+        let shifted = |rank: usize| (rank + offset) % len;
+        name.chars()
+            .map(|c| match *self {
+                _ if shift == 0 => c,
+                Alphabet::Ascii if c.is_ascii_lowercase() => {
+                    (b'a' + shifted((c as u8 - b'a') as usize) as u8) as char
                 }
-                counts
+                Alphabet::Unicode if c.is_alphabetic() => {
+                    unicode_letters(c, forwards)
+                        .nth(shift - 1)
+                        .expect("shifts are within the alphabet")
+                }
+                Alphabet::Custom(ref letters) => {
+                    letters.iter()
+                        .position(|&letter| letter == c)
+                        .map_or(c, |rank| letters[shifted(rank)])
+                }
+                _ => c,
             })
-            .into_iter()
-            .collect::<Vec<_>>();
-        char_counts.sort_by(|a, b| {
-            use std::cmp::Ordering;
-            let ordering = b.1.cmp(&a.1);
-            match ordering {
-                Ordering::Equal => a.0.cmp(&b.0),
-                other => other,
+            .collect()
+    }
+
+    /// Describe the letters of the alphabet, for errors.
+    fn describe(&self) -> &'static str {
+        match *self {
+            Alphabet::Ascii => "a lowercase letter",
+            _ => "a letter from the alphabet",
+        }
+    }
+}
+
+/// Get the Unicode letters after `c` in code point order, or before it
+/// in reverse order, wrapping around at the ends and stopping short of
+/// `c` itself.
+fn unicode_letters(c: char, forwards: bool) -> Box<dyn Iterator<Item = char>> {
+    let (before, after) = (0..c as u32, c as u32 + 1..=char::MAX as u32);
+    let code_points: Box<dyn Iterator<Item = u32>> = if forwards {
+        Box::new(after.chain(before))
+    } else {
+        Box::new(before.rev().chain(after.rev()))
+    };
+    Box::new(code_points.filter_map(char::from_u32).filter(|c| c.is_alphabetic()))
+}
+
+/// The rules for checking room names against their checksums.
+///
+/// The default policy is the one used by the kiosk: the five most
+/// common lowercase ASCII letters, with ties broken alphabetically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumPolicy {
+    length: usize,
+    tie_break: TieBreak,
+    alphabet: Alphabet,
+}
+
+impl Default for ChecksumPolicy {
+    fn default() -> Self {
+        ChecksumPolicy {
+            length: CHECKSUM_LEN,
+            tie_break: TieBreak::Alphabetical,
+            alphabet: Alphabet::Ascii,
+        }
+    }
+}
+
+impl ChecksumPolicy {
+    /// Set the number of letters in a checksum. Names with fewer
+    /// different letters have shorter checksums.
+    ///
+    /// # Panics
+    ///
+    /// If the length is zero.
+    pub fn with_length(mut self, length: usize) -> Self {
+        assert!(length > 0, "checksums must have at least one letter");
+        self.length = length;
+        self
+    }
+
+    /// Set how ties between letters are broken.
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Set the letters names and checksums are made of.
+    pub fn with_alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// Check if letter `a` comes before letter `b` in a checksum.
+    fn order(&self, a: &Tally, b: &Tally) -> Ordering {
+        b.count.cmp(&a.count).then_with(|| match self.tie_break {
+            TieBreak::Alphabetical => a.rank.cmp(&b.rank),
+            TieBreak::Reverse => b.rank.cmp(&a.rank),
+            TieBreak::FirstOccurrence => a.first.cmp(&b.first),
+        })
+    }
+}
+
+/// How often a letter appears in a name.
+#[derive(Debug, Clone, Copy, Default)]
+struct Tally {
+    /// The letter's position in the alphabet.
+    rank: usize,
+    count: u32,
+    /// The index of the first character in the name which is this
+    /// letter.
+    first: usize,
+}
+
+/// Count how many times each lowercase ASCII letter appears in a name.
+fn ascii_tallies(name: &str) -> [Tally; 26] {
+    let mut tallies = [Tally::default(); 26];
+    for (rank, tally) in tallies.iter_mut().enumerate() {
+        tally.rank = rank;
+    }
+    for (index, b) in name.bytes().enumerate() {
+        if let Some(tally) = tallies.get_mut(b.wrapping_sub(b'a') as usize) {
+            if tally.count == 0 {
+                tally.first = index;
             }
-        });
-        let checksum = char_counts.iter().map(|count| count.0).take(5).collect::<String>();
-        self.checksum == checksum
+            tally.count += 1;
+        }
+    }
+    tallies
+}
+
+/// Get the checksum letters for a name made of lowercase ASCII
+/// letters, without allocating.
+fn ascii_checksum<'a>(name: &str, policy: &'a ChecksumPolicy) -> impl Iterator<Item = char> + 'a {
+    let mut tallies = ascii_tallies(name);
+    (0..policy.length).map_while(move |_| {
+        let tally = tallies.iter_mut()
+            .filter(|tally| tally.count > 0)
+            .min_by(|a, b| policy.order(a, b))?;
+        tally.count = 0;
+        Some((b'a' + tally.rank as u8) as char)
+    })
+}
+
+/// Compute the checksum for a room name with a given policy.
+pub fn compute_checksum_with(name: &str, policy: &ChecksumPolicy) -> String {
+    if policy.alphabet == Alphabet::Ascii {
+        return ascii_checksum(name, policy).collect();
+    }
+    let mut tallies = BTreeMap::new();
+    for (index, c) in name.chars().enumerate() {
+        if let Some(rank) = policy.alphabet.rank(c) {
+            let tally = tallies.entry(c).or_insert(Tally {
+                rank,
+                count: 0,
+                first: index,
+            });
+            tally.count += 1;
+        }
+    }
+    let mut tallies = tallies.into_iter().collect::<Vec<_>>();
+    tallies.sort_by(|a, b| policy.order(&a.1, &b.1));
+    tallies.into_iter().map(|(c, _)| c).take(policy.length).collect()
+}
+
+/// Compute the checksum for a room name.
+///
+/// The checksum is the five most common letters in the name, most
+/// common first with ties broken alphabetically. Names with fewer than
+/// five different letters have shorter checksums.
+pub fn compute_checksum(name: &str) -> String {
+    compute_checksum_with(name, &ChecksumPolicy::default())
+}
+
+impl<'a> Room<'a> {
+    /// Parse a room from a string, with names and checksums made of
+    /// letters from the policy's alphabet.
+    pub fn parse_with(s: &'a str, policy: &ChecksumPolicy) -> Result<Room<'a>, ParseError> {
+        let column = |rest: &str| column_at(s, rest);
+        let is_letter = |c: char| policy.alphabet.contains(c);
+        let name_end = s.find(|c: char| !(c == '-' || is_letter(c))).unwrap_or(s.len());
+        let (name, rest) = s.split_at(name_end);
+        let name = match name.strip_suffix('-') {
+            Some(name) if !name.is_empty() => name,
+            _ => {
+                let expected = if name.is_empty() { "a room name" } else { "a letter or '-'" };
+                return Err(match rest.chars().next() {
+                    Some(c) => ParseError::unexpected_char(column(rest), expected, c),
+                    None => ParseError::unexpected_end(column(rest), expected),
+                });
+            }
+        };
+        let (sector, rest) = read_number::<u32>(rest, column(rest)).map_err(|e| {
+                match e {
+                    ParseError::Unexpected { column, ref expected, ref found, .. }
+                        if expected == "a smaller number" => {
+                        ParseError::unexpected(column, "a smaller sector ID", &found[..])
+                    }
+                    e => e,
+                }
+            })?;
+        let rest = expect_str(rest, column(rest), "[")?;
+        let checksum_len = rest.find(|c: char| !is_letter(c)).unwrap_or(rest.len());
+        let (checksum, rest) = rest.split_at(checksum_len);
+        if checksum.is_empty() {
+            return Err(match rest.chars().next() {
+                Some(c) => ParseError::unexpected_char(column(rest), "a checksum", c),
+                None => ParseError::unexpected_end(column(rest), "a checksum"),
+            });
+        }
+        let rest = expect_str(rest, column(rest), "]")?;
+        if let Some(c) = rest.chars().next() {
+            return Err(ParseError::unexpected_char(column(rest), "end of line", c));
+        }
+        Ok(Room {
+            name,
+            sector,
+            checksum,
+        })
     }
 
-    fn decrypt_char(c: char, shift: u32) -> char {
-        if c == '-' {
-            return ' ';
+    /// Encrypt a plaintext room name, building a room string with the
+    /// given sector ID and a valid checksum.
+    ///
+    /// The plaintext must be made of lowercase letters and spaces, and
+    /// contain at least one letter. This is the inverse of
+    /// `decrypt_name`.
+    pub fn encrypt(plaintext: &str, sector: u32) -> Result<String, ParseError> {
+        Room::encrypt_with(plaintext, sector, &ChecksumPolicy::default())
+    }
+
+    /// Encrypt a plaintext room name, building a room string with the
+    /// given sector ID and a checksum which is valid under a given
+    /// policy.
+    ///
+    /// The plaintext must be made of letters from the policy's alphabet
+    /// and spaces, and contain at least one letter. Letters are shifted
+    /// backwards within the alphabet, so this is the inverse of
+    /// `decrypt_name_with`.
+    pub fn encrypt_with(plaintext: &str,
+                        sector: u32,
+                        policy: &ChecksumPolicy)
+                        -> Result<String, ParseError> {
+        let alphabet = &policy.alphabet;
+        let mut name = String::with_capacity(plaintext.len());
+        for (i, c) in plaintext.chars().enumerate() {
+            name.push(match c {
+                ' ' => '-',
+                c if alphabet.contains(c) => c,
+                _ => {
+                    let expected = format!("{} or space", alphabet.describe());
+                    return Err(ParseError::unexpected_char(i + 1, expected, c));
+                }
+            });
+        }
+        if !name.chars().any(|c| alphabet.contains(c)) {
+            return Err(ParseError::unexpected_end(plaintext.chars().count() + 1,
+                                                  alphabet.describe()));
+        }
+        let name = alphabet.shift(&name, sector, false);
+        let checksum = compute_checksum_with(&name, policy);
+        Ok(format!("{}-{}[{}]", name, sector, checksum))
+    }
+
+    /// Test if a room's checksum is valid.
+    pub fn is_valid(&self) -> bool {
+        self.is_valid_with(&ChecksumPolicy::default())
+    }
+
+    /// Test if a room's checksum is valid under a given policy.
+    pub fn is_valid_with(&self, policy: &ChecksumPolicy) -> bool {
+        if policy.alphabet == Alphabet::Ascii {
+            ascii_checksum(self.name, policy).eq(self.checksum.chars())
+        } else {
+            compute_checksum_with(self.name, policy) == self.checksum
         }
-        let shift = (shift % 26) as u8;
-        ('a' as u8 + (((c as u8 - 'a' as u8) + shift) % 26)) as char
     }
 
     /// Decrypt name
     pub fn decrypt_name(&self) -> String {
-        self.name.chars().map(|c| Self::decrypt_char(c, self.sector)).collect()
+        self.decrypt_with(&Caesar(self.sector))
+    }
+
+    /// Decrypt the name by shifting its letters forwards within the
+    /// policy's alphabet, turning dashes into spaces.
+    pub fn decrypt_name_with(&self, policy: &ChecksumPolicy) -> String {
+        policy.alphabet.shift(self.name, self.sector, true).replace('-', " ")
+    }
+
+    /// Decrypt the name with a given cipher.
+    pub fn decrypt_with<C: Cipher + ?Sized>(&self, cipher: &C) -> String {
+        cipher.decrypt(self.name)
+    }
+}
+
+/// A cipher for room names.
+///
+/// Ciphers work on one letter at a time, with letters numbered from
+/// `0` for `a` to `25` for `z`. The `index` of each letter counts only
+/// the letters in the name, so dashes don't affect the key.
+pub trait Cipher {
+    /// Decrypt a single letter.
+    fn decrypt_letter(&self, letter: u8, index: usize) -> u8;
+
+    /// Encrypt a single letter.
+    fn encrypt_letter(&self, letter: u8, index: usize) -> u8;
+
+    /// Decrypt a room name, turning dashes into spaces.
+    fn decrypt(&self, name: &str) -> String {
+        map_letters(name, '-', ' ', |letter, index| self.decrypt_letter(letter, index))
+    }
+
+    /// Encrypt a plaintext name, turning spaces into dashes.
+    fn encrypt(&self, plaintext: &str) -> String {
+        map_letters(plaintext, ' ', '-', |letter, index| self.encrypt_letter(letter, index))
+    }
+}
+
+/// Map each lowercase letter in a string, replacing one separator with
+/// another. Other characters are left alone.
+fn map_letters<F>(s: &str, from: char, to: char, mut map: F) -> String
+    where F: FnMut(u8, usize) -> u8
+{
+    let mut index = 0;
+    s.chars()
+        .map(|c| match c {
+            'a'..='z' => {
+                let letter = map(c as u8 - b'a', index);
+                index += 1;
+                (b'a' + letter) as char
+            }
+            c if c == from => to,
+            c => c,
+        })
+        .collect()
+}
+
+/// Shift each letter forwards by a fixed amount to decrypt it. This is
+/// the cipher the rooms use, with their sector ID as the shift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Caesar(pub u32);
+
+impl Cipher for Caesar {
+    fn decrypt_letter(&self, letter: u8, _index: usize) -> u8 {
+        (letter + (self.0 % 26) as u8) % 26
+    }
+
+    fn encrypt_letter(&self, letter: u8, _index: usize) -> u8 {
+        (letter + 26 - (self.0 % 26) as u8) % 26
+    }
+}
+
+/// Shift each letter by the next letter of a repeating key. Encrypting
+/// shifts forwards, so a key of `b` encrypts `a` as `b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vigenere {
+    key: Vec<u8>,
+}
+
+impl Vigenere {
+    /// Create a cipher with the given key, which must be one or more
+    /// lowercase letters.
+    pub fn new(key: &str) -> Result<Self, ParseError> {
+        let key = key.chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                'a'..='z' => Ok(c as u8 - b'a'),
+                _ => Err(ParseError::unexpected_char(i + 1, "a lowercase letter", c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if key.is_empty() {
+            return Err(ParseError::unexpected_end(1, "a lowercase letter"));
+        }
+        Ok(Vigenere { key })
+    }
+
+    /// Get the key.
+    pub fn key(&self) -> String {
+        self.key.iter().map(|&letter| (b'a' + letter) as char).collect()
+    }
+}
+
+impl Cipher for Vigenere {
+    fn decrypt_letter(&self, letter: u8, index: usize) -> u8 {
+        (letter + 26 - self.key[index % self.key.len()]) % 26
+    }
+
+    fn encrypt_letter(&self, letter: u8, index: usize) -> u8 {
+        (letter + self.key[index % self.key.len()]) % 26
+    }
+}
+
+/// Reverse the alphabet, so `a` becomes `z` and `z` becomes `a`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Atbash;
+
+impl Cipher for Atbash {
+    fn decrypt_letter(&self, letter: u8, _index: usize) -> u8 {
+        25 - letter
+    }
+
+    fn encrypt_letter(&self, letter: u8, _index: usize) -> u8 {
+        25 - letter
+    }
+}
+
+/// Encrypt each letter `x` as `a * x + b`, modulo 26.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Affine {
+    a: u8,
+    b: u8,
+    /// The multiplicative inverse of `a`, used to decrypt.
+    inverse: u8,
+}
+
+impl Affine {
+    /// Create an affine cipher. Returns `None` if `a` shares a factor
+    /// with 26, as then letters can't be decrypted.
+    pub fn new(a: u32, b: u32) -> Option<Self> {
+        let a = (a % 26) as u8;
+        let inverse = (1..26).find(|&inverse| (a as u32 * inverse as u32) % 26 == 1)?;
+        Some(Affine {
+            a,
+            b: (b % 26) as u8,
+            inverse,
+        })
+    }
+}
+
+impl Cipher for Affine {
+    fn decrypt_letter(&self, letter: u8, _index: usize) -> u8 {
+        ((self.inverse as u32 * (letter + 26 - self.b) as u32) % 26) as u8
+    }
+
+    fn encrypt_letter(&self, letter: u8, _index: usize) -> u8 {
+        ((self.a as u32 * letter as u32 + self.b as u32) % 26) as u8
+    }
+}
+
+/// How often each letter appears in English text, in percent.
+const ENGLISH_FREQUENCIES: [f64; 26] = [8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094,
+                                        6.966, 0.153, 0.772, 4.025, 2.406, 6.749, 7.507, 1.929,
+                                        0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150,
+                                        1.974, 0.074];
+
+/// Measure how unlike English some letter counts are, after shifting
+/// them forwards by `shift`. Lower is more like English.
+fn chi_squared(counts: &[u32; 26], shift: usize) -> f64 {
+    let total = counts.iter().sum::<u32>() as f64;
+    (0..26)
+        .map(|letter| {
+            let observed = counts[(letter + 26 - shift) % 26] as f64;
+            let expected = total * ENGLISH_FREQUENCIES[letter] / 100.0;
+            (observed - expected).powi(2) / expected
+        })
+        .sum()
+}
+
+/// Find the shift which makes the letters in a name look most like
+/// English. Returns `None` if there are no letters to go on.
+fn best_shift(name: &str) -> Option<u32> {
+    let counts = ascii_tallies(name).map(|tally| tally.count);
+    if counts.iter().all(|&count| count == 0) {
+        return None;
+    }
+    (0..26)
+        .min_by(|&a, &b| {
+            chi_squared(&counts, a).partial_cmp(&chi_squared(&counts, b)).expect("scores are never NaN")
+        })
+        .map(|shift| shift as u32)
+}
+
+/// Guess the Caesar shift used to encrypt a name, by comparing letter
+/// frequencies against English. This finds the sector ID of a room
+/// modulo 26. Short names may not have enough letters to guess from,
+/// and names without any letters give `None`.
+pub fn crack_caesar(name: &str) -> Option<Caesar> {
+    best_shift(name).map(Caesar)
+}
+
+/// Guess the key of length `key_len` used to encrypt a name with a
+/// Vigenère cipher. Each letter of the key is guessed separately, so
+/// names need many more letters than the key for a good guess, and
+/// names with fewer letters than the key give `None`.
+///
+/// # Panics
+///
+/// If `key_len` is zero.
+pub fn crack_vigenere(name: &str, key_len: usize) -> Option<Vigenere> {
+    assert!(key_len > 0, "keys must have at least one letter");
+    let mut columns = vec![String::new(); key_len];
+    for (index, c) in name.chars().filter(char::is_ascii_lowercase).enumerate() {
+        columns[index % key_len].push(c);
+    }
+    let key = columns.iter()
+        .map(|column| best_shift(column).map(|shift| (26 - shift as u8) % 26))
+        .collect::<Option<_>>()?;
+    Some(Vigenere { key })
+}
+
+/// A search over decrypted room names.
+#[derive(Debug)]
+pub enum Query {
+    /// Names containing some text.
+    Substring(String),
+    /// Names matching a regular expression.
+    Pattern(Regex),
+    /// Names containing some text, give or take a number of edits.
+    Fuzzy { text: String, max_edits: usize },
+}
+
+/// A room found by a `Query`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Match<'a> {
+    pub room: Room<'a>,
+    /// How well the room matched, lower is better. For fuzzy queries
+    /// this is the number of edits needed. Otherwise it is the number
+    /// of characters of the name outside the match.
+    pub score: usize,
+}
+
+impl Query {
+    /// Search for names containing some text.
+    pub fn substring(text: &str) -> Self {
+        Query::Substring(text.to_string())
+    }
+
+    /// Search for names matching a regular expression.
+    pub fn pattern(pattern: &str) -> Result<Self, onig::Error> {
+        Ok(Query::Pattern(Regex::new(pattern)?))
+    }
+
+    /// Search for names containing some text, allowing up to
+    /// `max_edits` characters to be inserted, removed or changed.
+    pub fn fuzzy(text: &str, max_edits: usize) -> Self {
+        Query::Fuzzy {
+            text: text.to_string(),
+            max_edits,
+        }
+    }
+
+    /// Score a decrypted name against the query, if it matches.
+    pub fn score(&self, name: &str) -> Option<usize> {
+        match *self {
+            Query::Substring(ref text) => name.find(&text[..]).map(|_| name.len() - text.len()),
+            Query::Pattern(ref regex) => regex.find(name).map(|(start, end)| name.len() - (end - start)),
+            Query::Fuzzy { ref text, max_edits } => {
+                Some(edit_distance_within(name, text)).filter(|&edits| edits <= max_edits)
+            }
+        }
+    }
+
+    /// Search some rooms, returning the ones which match ranked best
+    /// first. Rooms with the same score are kept in order.
+    pub fn search<'a, I>(&self, rooms: I) -> Vec<Match<'a>>
+        where I: IntoIterator<Item = Room<'a>>
+    {
+        let mut matches = rooms.into_iter()
+            .filter_map(|room| {
+                self.score(&room.decrypt_name()).map(|score| Match { room, score })
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|m| m.score);
+        matches
+    }
+}
+
+/// Find the fewest edits needed to make `text` appear somewhere in
+/// `name`.
+fn edit_distance_within(name: &str, text: &str) -> usize {
+    let text = text.chars().collect::<Vec<_>>();
+    // The edits needed for each prefix of the text to end at the
+    // current position in the name. Matches can start anywhere, so the
+    // empty prefix is always free.
+    let mut edits = (0..=text.len()).collect::<Vec<_>>();
+    let mut best = text.len();
+    for c in name.chars() {
+        let mut diagonal = edits[0];
+        for (i, &want) in text.iter().enumerate() {
+            let changed = diagonal + if want == c { 0 } else { 1 };
+            diagonal = edits[i + 1];
+            edits[i + 1] = changed.min(edits[i] + 1).min(diagonal + 1);
+        }
+        best = best.min(edits[text.len()]);
+    }
+    best
+}
+
+/// A list of rooms at the information kiosk.
+pub struct Rooms(String);
+
+impl Rooms {
+    /// Get the rooms with valid checksums.
+    fn real_rooms(&self) -> Vec<Room<'_>> {
+        self.0
+            .lines()
+            .map(|line| Room::try_from(line).expect("rooms are checked when parsed"))
+            .filter(|room| room.is_valid())
+            .collect()
+    }
+}
+
+impl Solution for Rooms {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        for (i, line) in input.lines().enumerate() {
+            Room::try_from(line).map_err(|e| e.at_line(i + 1))?;
+        }
+        Ok(Rooms(input.to_string()))
+    }
+
+    fn part1(&self) -> Box<dyn fmt::Display> {
+        Box::new(self.real_rooms().iter().map(|room| room.sector).sum::<u32>())
+    }
+
+    fn part2(&self) -> Box<dyn fmt::Display> {
+        match self.real_rooms().iter().find(|room| room.decrypt_name().contains("northpole")) {
+            Some(room) => Box::new(room.sector),
+            None => Box::new("no north pole room"),
+        }
     }
 }
 
@@ -75,16 +808,20 @@ mod test {
 
     use super::*;
 
+    fn room(s: &str) -> Room<'_> {
+        Room::try_from(s).unwrap()
+    }
+
     #[test]
     fn parse_room_string() {
         {
-            let r = Room::from("aaaaa-bbb-z-y-x-123[abxyz]");
+            let r = room("aaaaa-bbb-z-y-x-123[abxyz]");
             assert_eq!("aaaaa-bbb-z-y-x", r.name);
             assert_eq!(123, r.sector);
             assert_eq!("abxyz", r.checksum);
         }
         {
-            let r = Room::from("a-b-c-d-e-f-g-h-987[abcde]");
+            let r = room("a-b-c-d-e-f-g-h-987[abcde]");
             assert_eq!("a-b-c-d-e-f-g-h", r.name);
             assert_eq!(987, r.sector);
             assert_eq!("abcde", r.checksum);
@@ -93,19 +830,379 @@ mod test {
 
     #[test]
     fn is_valid() {
-        assert!(Room::from("aaaaa-bbb-z-y-x-123[abxyz]").is_valid());
-        assert!(Room::from("a-b-c-d-e-f-g-h-987[abcde]").is_valid());
-        assert!(Room::from("not-a-real-room-404[oarel]").is_valid());
+        assert!(room("aaaaa-bbb-z-y-x-123[abxyz]").is_valid());
+        assert!(room("a-b-c-d-e-f-g-h-987[abcde]").is_valid());
+        assert!(room("not-a-real-room-404[oarel]").is_valid());
     }
 
     #[test]
     fn invalid_checksums() {
-        assert!(!Room::from("totally-real-room-200[decoy]").is_valid());
+        assert!(!room("totally-real-room-200[decoy]").is_valid());
     }
 
     #[test]
     fn test_decrypt_name() {
         assert_eq!("very encrypted name",
-                   Room::from("qzmt-zixmtkozy-ivhz-343[abcdef]").decrypt_name());
+                   room("qzmt-zixmtkozy-ivhz-343[abcdef]").decrypt_name());
+    }
+
+    #[test]
+    fn solve_rooms() {
+        let rooms = Rooms::parse("aaaaa-bbb-z-y-x-123[abxyz]
+a-b-c-d-e-f-g-h-987[abcde]
+not-a-real-room-404[oarel]
+totally-real-room-200[decoy]
+ghkmaihex-hucxvm-lmhktzx-501[hmxka]")
+            .unwrap();
+        assert_eq!("2015", rooms.part1().to_string());
+        assert_eq!("501", rooms.part2().to_string());
+    }
+
+    #[test]
+    fn parse_invalid_rooms() {
+        assert!(Room::try_from("not a room").is_err());
+        let err = Room::try_from("abc-99999999999[abc]").err().unwrap();
+        assert_eq!(5, err.column());
+        let err = Rooms::parse("abc-123[abc]\nnope").err().unwrap();
+        assert_eq!(2, err.line());
+    }
+
+    #[test]
+    fn parse_room_errors() {
+        let err = |s| Room::try_from(s).err().unwrap();
+        assert_eq!(ParseError::unexpected_char(1, "a room name", '1'), err("123[abc]"));
+        assert_eq!(ParseError::unexpected_char(4, "a letter or '-'", '1'), err("abc123[abc]"));
+        assert_eq!(ParseError::unexpected_char(5, "a number", '['), err("abc-[abc]"));
+        assert_eq!(ParseError::unexpected(5, "a smaller sector ID", "99999999999"),
+                   err("abc-99999999999[abc]"));
+        assert_eq!(ParseError::unexpected_char(8, "\"[\"", '('), err("abc-123(abc)"));
+        assert_eq!(ParseError::unexpected_char(9, "a checksum", ']'), err("abc-123[]"));
+        assert_eq!(ParseError::unexpected_end(12, "\"]\""), err("abc-123[abc"));
+        assert_eq!(ParseError::unexpected_char(13, "end of line", '!'), err("abc-123[abc]!"));
+    }
+
+    #[test]
+    fn compute_checksums() {
+        assert_eq!("abxyz", compute_checksum("aaaaa-bbb-z-y-x"));
+        assert_eq!("oarel", compute_checksum("not-a-real-room"));
+        assert_eq!("ba", compute_checksum("a-bb"));
+        assert_eq!("", compute_checksum("--"));
+    }
+
+    #[test]
+    fn encrypt_names() {
+        let encrypted = Room::encrypt("very encrypted name", 343).unwrap();
+        assert_eq!("qzmt-zixmtkozy-ivhz-343[zimth]", encrypted);
+        assert!(room(&encrypted).is_valid());
+        assert_eq!(Err(ParseError::unexpected_char(7, "a lowercase letter or space", 'X')),
+                   Room::encrypt("north Xpole", 1));
+        assert_eq!(Err(ParseError::unexpected_end(3, "a lowercase letter")),
+                   Room::encrypt("  ", 1));
+    }
+
+    #[test]
+    fn encrypt_round_trip() {
+        let words = ["north", "pole", "object", "storage", "candy", "a", "zz"];
+        for sector in (0..60).chain(vec![u32::MAX]) {
+            let plaintext = (0..(sector as usize % 4) + 1)
+                .map(|i| words[(sector as usize + i * 3) % words.len()])
+                .collect::<Vec<_>>()
+                .join(" ");
+            let encrypted = Room::encrypt(&plaintext, sector).unwrap();
+            let r = room(&encrypted);
+            assert!(r.is_valid(), "{}", encrypted);
+            assert_eq!(sector, r.sector);
+            assert_eq!(plaintext, r.decrypt_name());
+        }
+    }
+
+    #[test]
+    fn encrypt_with_policies() {
+        let policies = [ChecksumPolicy::default().with_length(3),
+                        ChecksumPolicy::default().with_tie_break(TieBreak::Reverse),
+                        ChecksumPolicy::default()
+                            .with_tie_break(TieBreak::FirstOccurrence)
+                            .with_length(10)];
+        for policy in &policies {
+            for sector in [0, 1, 343, 501] {
+                let encrypted = Room::encrypt_with("northpole object storage", sector, policy)
+                    .unwrap();
+                let r = room(&encrypted);
+                assert!(r.is_valid_with(policy), "{}", encrypted);
+                assert_eq!("northpole object storage", r.decrypt_name());
+            }
+        }
+        assert_eq!("qzmt-zixmtkozy-ivhz-343[ztmiy]",
+                   Room::encrypt_with("very encrypted name", 343, &policies[1]).unwrap());
+    }
+
+    #[test]
+    fn encrypt_within_alphabets() {
+        let cases = [(ChecksumPolicy::default(), "northpole object storage"),
+                     (ChecksumPolicy::default().with_alphabet(Alphabet::custom("zyx")), "zy xxz"),
+                     (ChecksumPolicy::default().with_alphabet(Alphabet::Unicode), "ñandú ñu")];
+        for &(ref policy, plaintext) in &cases {
+            for sector in [0, 1, 2, 343, 501] {
+                let encrypted = Room::encrypt_with(plaintext, sector, policy).unwrap();
+                let r = Room::parse_with(&encrypted, policy).unwrap();
+                assert!(r.is_valid_with(policy), "{}", encrypted);
+                assert_eq!(plaintext, r.decrypt_name_with(policy));
+            }
+        }
+        let policy = &cases[1].0;
+        assert_eq!("xz-1[zx]", Room::encrypt_with("zy", 1, policy).unwrap());
+        let expected = "a letter from the alphabet or space";
+        assert_eq!(Err(ParseError::unexpected_char(1, expected, 'a')),
+                   Room::encrypt_with("ab", 1, policy));
+        assert_eq!(Err(ParseError::unexpected_end(2, "a letter from the alphabet")),
+                   Room::encrypt_with(" ", 1, policy));
+    }
+
+    #[test]
+    fn owned_rooms() {
+        let owned = {
+            let input = String::from("not-a-real-room-404[oarel]");
+            RoomBuf::from(room(&input))
+        };
+        assert_eq!("not-a-real-room", owned.name);
+        assert_eq!(404, owned.sector);
+        assert_eq!("oarel", owned.checksum);
+        assert!(owned.as_room().is_valid());
+        assert_eq!(Ok(owned.clone()), "not-a-real-room-404[oarel]".parse());
+        assert_eq!(room("not-a-real-room-404[oarel]"), Room::from(&owned));
+        assert!("not a room".parse::<RoomBuf>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialise_rooms() {
+        extern crate csv;
+        extern crate serde_json;
+
+        let owned = RoomBuf::from(room("qzmt-zixmtkozy-ivhz-343[zimth]"));
+        let json = serde_json::to_string(&owned).unwrap();
+        assert_eq!(r#"{"name":"qzmt-zixmtkozy-ivhz","sector":343,"checksum":"zimth","valid":true,"decrypted":"very encrypted name"}"#,
+                   json);
+        assert_eq!(json,
+                   serde_json::to_string(&room("qzmt-zixmtkozy-ivhz-343[zimth]")).unwrap());
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.serialize(&owned).unwrap();
+        writer.serialize(room("totally-real-room-200[decoy]")).unwrap();
+        let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!("name,sector,checksum,valid,decrypted
+qzmt-zixmtkozy-ivhz,343,zimth,true,very encrypted name
+totally-real-room,200,decoy,false,lglsddq jwsd jgge
+",
+                   csv);
+    }
+
+    #[test]
+    fn decrypt_with_ciphers() {
+        let r = room("qzmt-zixmtkozy-ivhz-343[zimth]");
+        assert_eq!(r.decrypt_name(), r.decrypt_with(&Caesar(343)));
+        assert_eq!("very encrypted name", r.decrypt_with(&Caesar(343 % 26)));
+        assert_eq!("zyx wvu", Atbash.decrypt("abc-def"));
+        assert_eq!("abc-def", Atbash.encrypt(&Atbash.decrypt("abc-def")));
+
+        let vigenere = Vigenere::new("lemon").unwrap();
+        assert_eq!("lxfopv-ef-rnhr", vigenere.encrypt("attack at dawn"));
+        assert_eq!("attack at dawn", room("lxfopv-ef-rnhr-1[rfhln]").decrypt_with(&vigenere));
+        assert_eq!("lemon", vigenere.key());
+        assert_eq!(Err(ParseError::unexpected_char(3, "a lowercase letter", 'M')),
+                   Vigenere::new("leMon"));
+        assert_eq!(Err(ParseError::unexpected_end(1, "a lowercase letter")), Vigenere::new(""));
+
+        let affine = Affine::new(5, 8).unwrap();
+        assert_eq!("ihhwvc-swfrcp", affine.encrypt("affine cipher"));
+        assert_eq!("affine cipher", affine.decrypt("ihhwvc-swfrcp"));
+        assert_eq!(None, Affine::new(13, 1));
+        assert_eq!(None, Affine::new(2, 1));
+
+        let ciphers: Vec<Box<dyn Cipher>> = vec![Box::new(Caesar(3)), Box::new(Atbash)];
+        let decrypted = ciphers.iter().map(|c| r.decrypt_with(&**c)).collect::<Vec<_>>();
+        assert_eq!(vec!["tcpw clapwnrcb lykc", "jang arcngplab resa"], decrypted);
+    }
+
+    const PLAINTEXT: &str = "it was the best of times it was the worst of times it was the age \
+                             of wisdom it was the age of foolishness it was the epoch of belief \
+                             it was the epoch of incredulity it was the season of light";
+
+    #[test]
+    fn crack_shifts() {
+        for sector in [0, 7, 25, 343] {
+            let name = Caesar(sector).encrypt(PLAINTEXT);
+            assert_eq!(Some(Caesar(sector % 26)), crack_caesar(&name));
+        }
+        let encrypted = Room::encrypt("northpole object storage", 501).unwrap();
+        let r = room(&encrypted);
+        assert_eq!("northpole object storage", r.decrypt_with(&crack_caesar(r.name).unwrap()));
+        assert_eq!(None, crack_caesar(""));
+        assert_eq!(None, crack_caesar("--"));
+    }
+
+    #[test]
+    fn crack_keys() {
+        let name = Vigenere::new("dickens").unwrap().encrypt(PLAINTEXT);
+        assert_eq!("dickens", crack_vigenere(&name, 7).unwrap().key());
+        let name = Vigenere::new("key").unwrap().encrypt(PLAINTEXT);
+        assert_eq!("key", crack_vigenere(&name, 3).unwrap().key());
+        assert_eq!(None, crack_vigenere("", 1));
+        assert_eq!(None, crack_vigenere("abc", 5));
+        assert_eq!(None, crack_vigenere("qzmt-zixmtkozy-ivhz", 20));
+        assert!(crack_vigenere("qzmt-zixmtkozy-ivhz", 16).is_some());
+    }
+
+    fn decrypted(matches: &[Match]) -> Vec<(String, usize)> {
+        matches.iter().map(|m| (m.room.decrypt_name(), m.score)).collect()
+    }
+
+    const ROOMS: &[&str] = &["ghkmaihex-hucxvm-lmhktzx-501[hmxka]",
+                             "qzmt-zixmtkozy-ivhz-343[zimth]",
+                             "bnvm-hyvq-xkrve-23[vbekm]"];
+
+    #[test]
+    fn search_substrings() {
+        let rooms = ROOMS.iter().map(|s| room(s));
+        assert_eq!(vec![("northpole object storage".to_string(), 15)],
+                   decrypted(&Query::substring("northpole").search(rooms.clone())));
+        assert_eq!(vec![("yksj evsn uhosb".to_string(), 14),
+                        ("very encrypted name".to_string(), 18),
+                        ("northpole object storage".to_string(), 23)],
+                   decrypted(&Query::substring("e").search(rooms.clone())));
+        assert!(Query::substring("south").search(rooms).is_empty());
+    }
+
+    #[test]
+    fn search_patterns() {
+        let rooms = ROOMS.iter().map(|s| room(s));
+        assert_eq!(vec![("very encrypted name".to_string(), 10),
+                        ("northpole object storage".to_string(), 18)],
+                   decrypted(&Query::pattern("[a-z]+ed|ob[a-z]+").unwrap().search(rooms)));
+        assert!(Query::pattern("(unclosed").is_err());
+    }
+
+    #[test]
+    fn search_fuzzy() {
+        let owned = ROOMS.iter().map(|s| s.parse::<RoomBuf>().unwrap()).collect::<Vec<_>>();
+        let rooms = || owned.iter().map(RoomBuf::as_room);
+        assert_eq!(vec![("northpole object storage".to_string(), 1)],
+                   decrypted(&Query::fuzzy("nort pole", 1).search(rooms())));
+        assert_eq!(vec![("very encrypted name".to_string(), 1)],
+                   decrypted(&Query::fuzzy("encripted", 2).search(rooms())));
+        assert_eq!(3, Query::fuzzy("xyz", 10).search(rooms()).len());
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(0, edit_distance_within("northpole", "pole"));
+        assert_eq!(1, edit_distance_within("northpole", "nrth"));
+        assert_eq!(1, edit_distance_within("northpole", "northxpole"));
+        assert_eq!(2, edit_distance_within("abc", "axcy"));
+        assert_eq!(3, edit_distance_within("", "abc"));
+        assert_eq!(0, edit_distance_within("abc", ""));
+    }
+
+    #[test]
+    fn checksum_policies() {
+        let name = "bbb-aa-cc-d-e-f-g";
+        let policy = ChecksumPolicy::default();
+        assert_eq!("bacde", compute_checksum_with(name, &policy));
+        let policy = policy.with_length(3);
+        assert_eq!("bac", compute_checksum_with(name, &policy));
+        let policy = policy.with_tie_break(TieBreak::Reverse);
+        assert_eq!("bca", compute_checksum_with(name, &policy));
+        let policy = policy.with_tie_break(TieBreak::FirstOccurrence).with_length(10);
+        assert_eq!("bcadefg", compute_checksum_with("bbb-cc-aa-d-e-f-g", &policy));
+        assert!(room("bbb-cc-aa-d-e-f-g-1[bcadefg]").is_valid_with(&policy));
+        assert!(!room("bbb-cc-aa-d-e-f-g-1[bcadefg]").is_valid());
+    }
+
+    #[test]
+    #[should_panic(expected = "checksums must have at least one letter")]
+    fn empty_checksums() {
+        ChecksumPolicy::default().with_length(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "alphabets must have at least one letter")]
+    fn empty_alphabets() {
+        Alphabet::custom("");
+    }
+
+    #[test]
+    #[should_panic(expected = "alphabets can't contain dashes, digits or brackets")]
+    fn alphabets_without_separators() {
+        Alphabet::custom("ab-c");
+    }
+
+    #[test]
+    fn unicode_alphabets() {
+        let policy = ChecksumPolicy::default().with_alphabet(Alphabet::Unicode);
+        let r = Room::parse_with("ñandú-ñu-éé-42[éñadnu]", &policy).unwrap();
+        assert_eq!("ñandú-ñu-éé", r.name);
+        assert_eq!("éñadn", compute_checksum_with(r.name, &policy));
+        assert!(!r.is_valid_with(&policy));
+        let policy = policy.with_length(6);
+        assert!(r.is_valid_with(&policy));
+        assert_eq!(Err(ParseError::unexpected_char(1, "a room name", 'ñ')),
+                   Room::try_from("ñandú-42[ñadnú]"));
+        assert_eq!(Err(ParseError::unexpected_char(13, "a checksum", '!')),
+                   Room::parse_with("ñandú-ñu-42[!]", &policy));
+    }
+
+    #[test]
+    fn custom_alphabets() {
+        // Vowels first, then the rest of the letters.
+        let policy = ChecksumPolicy::default()
+            .with_alphabet(Alphabet::custom("aeioubcdfghjklmnpqrstvwxyz"));
+        assert_eq!("ebcdf", compute_checksum_with("f-d-c-b-e", &policy));
+        let policy = ChecksumPolicy::default().with_alphabet(Alphabet::custom("αβγ"));
+        let r = Room::parse_with("γγ-αβ-7[γαβ]", &policy).unwrap();
+        assert!(r.is_valid_with(&policy));
+        assert!(Room::parse_with("γδ-7[γ]", &policy).is_err());
+    }
+
+    /// The checksum as it was first written, using a `HashMap`.
+    fn reference_checksum(name: &str) -> String {
+        let mut counts = name.replace("-", "")
+            .chars()
+            .fold(::std::collections::HashMap::new(), |mut counts, c| {
+                *counts.entry(c).or_insert(0) += 1;
+                counts
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts.iter().map(|count| count.0).take(5).collect()
+    }
+
+    #[test]
+    fn checksums_match_reference() {
+        let mut seed = 42u32;
+        let mut next = |limit: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) % limit
+        };
+        for _ in 0..1000 {
+            let len = next(20) as usize;
+            let alphabet = next(26) + 1;
+            let name = (0..len)
+                .map(|_| match next(alphabet + 1) {
+                    0 => '-',
+                    n => (b'a' + n as u8 - 1) as char,
+                })
+                .collect::<String>();
+            let checksum = reference_checksum(&name);
+            let r = Room {
+                name: &name,
+                sector: 1,
+                checksum: &checksum,
+            };
+            assert!(r.is_valid(), "{:?}", name);
+            let mut wrong = checksum.clone();
+            wrong.push('z');
+            assert!(!Room { checksum: &wrong, ..r }.is_valid());
+        }
     }
 }
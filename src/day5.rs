@@ -2,9 +2,24 @@
 
 extern crate crypto;
 
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as FmtWrite};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::vec;
+
 use self::crypto::md5::Md5;
 use self::crypto::digest::Digest;
 
+use super::{ParseError, ReadError, Solution};
+use error::{column_at, expect_str, read_number};
+
 pub fn hash_for_suffix(door: &str, hasher: &mut Md5, suffix: i64) -> String {
     hasher.reset();
     hasher.input_str(door);
@@ -12,37 +27,385 @@ pub fn hash_for_suffix(door: &str, hasher: &mut Md5, suffix: i64) -> String {
     hasher.result_str()
 }
 
-pub fn get_password(door: &str) -> String {
-    let mut hasher = Md5::new();
-    (0..)
-        .filter_map(|i| {
-            let hash = hash_for_suffix(door, &mut hasher, i);
-            if hash.starts_with("00000") {
-                Some(hash)
-            } else {
-                None
+/// The number of leading zeros which make a hash interesting.
+const ZEROS: usize = 5;
+
+/// The most leading zeros a hash can be searched for with. Hashes have
+/// 32 digits, and the passwords need two more after the zeros.
+pub const MAX_ZEROS: usize = 30;
+
+/// The number of suffixes a worker thread checks at a time.
+const CHUNK_SIZE: i64 = 10_000;
+
+/// Get the number of worker threads to search with by default, one for
+/// each available core.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+/// A hash of the door ID and a suffix which starts with enough zeros,
+/// along with the suffix.
+pub type Hit = (i64, [u8; 16]);
+
+/// Get the hex digit at `index` in a digest.
+fn nibble(digest: &[u8; 16], index: usize) -> u8 {
+    let byte = digest[index / 2];
+    if index.is_multiple_of(2) { byte >> 4 } else { byte & 0xf }
+}
+
+/// Format a digest as hex.
+fn to_hex(digest: &[u8; 16]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hash the door ID with a suffix, returning the digest if it starts
+/// with `zeros` zeros.
+fn zero_hash(door: &str,
+             hasher: &mut Md5,
+             suffix_buf: &mut String,
+             suffix: i64,
+             zeros: usize)
+             -> Option<[u8; 16]> {
+    suffix_buf.clear();
+    write!(suffix_buf, "{}", suffix).expect("writing to a string can't fail");
+    hasher.reset();
+    hasher.input_str(door);
+    hasher.input_str(suffix_buf);
+    let mut digest = [0; 16];
+    hasher.result(&mut digest);
+    if (0..zeros).all(|index| nibble(&digest, index) == 0) {
+        Some(digest)
+    } else {
+        None
+    }
+}
+
+/// A stream of the hashes of a door ID and a suffix which start with
+/// enough zeros, in ascending suffix order.
+///
+/// Suffixes are handed out to worker threads in chunks when the stream
+/// is first read. Finished chunks are merged back in order, so hits
+/// come out the same however many threads are used. Workers only run a
+/// few chunks ahead of the reader.
+pub struct ZeroHashStream {
+    door: Arc<str>,
+    zeros: usize,
+    threads: usize,
+    start: i64,
+    workers: Option<Workers>,
+}
+
+impl ZeroHashStream {
+    /// Create a stream of hits for a door, looking for hashes which
+    /// start with five zeros using the default number of threads.
+    pub fn new(door: &str) -> Self {
+        ZeroHashStream {
+            door: Arc::from(door),
+            zeros: ZEROS,
+            threads: default_threads(),
+            start: 0,
+            workers: None,
+        }
+    }
+
+    /// Set the number of zeros hashes must start with.
+    ///
+    /// # Panics
+    ///
+    /// If there are more than `MAX_ZEROS` zeros.
+    pub fn with_zeros(mut self, zeros: usize) -> Self {
+        assert!(zeros <= MAX_ZEROS, "passwords need two digits after the zeros");
+        self.zeros = zeros;
+        self
+    }
+
+    /// Set the number of worker threads to search with.
+    ///
+    /// # Panics
+    ///
+    /// If the number of threads is zero.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "searches need at least one thread");
+        self.threads = threads;
+        self
+    }
+
+    /// Start searching from the given suffix, rather than zero.
+    pub fn starting_at(mut self, suffix: i64) -> Self {
+        self.start = suffix;
+        self
+    }
+
+    /// Get the door ID.
+    pub fn door(&self) -> &str {
+        &self.door
+    }
+
+    /// Get the number of zeros hashes must start with.
+    pub fn zeros(&self) -> usize {
+        self.zeros
+    }
+}
+
+impl Iterator for ZeroHashStream {
+    type Item = Hit;
+
+    fn next(&mut self) -> Option<Hit> {
+        if self.workers.is_none() {
+            self.workers = Some(Workers::start(&self.door, self.zeros, self.threads, self.start));
+        }
+        self.workers.as_mut().and_then(Workers::next)
+    }
+}
+
+/// The worker threads behind a `ZeroHashStream`.
+struct Workers {
+    receiver: mpsc::Receiver<(i64, Vec<Hit>)>,
+    done: Arc<AtomicBool>,
+    /// Chunks which have finished before the chunks ahead of them.
+    finished: BTreeMap<i64, Vec<Hit>>,
+    /// The next chunk to read hits from.
+    next_chunk: i64,
+    /// The rest of the hits in the current chunk.
+    current: vec::IntoIter<Hit>,
+}
+
+impl Workers {
+    fn start(door: &Arc<str>, zeros: usize, threads: usize, start: i64) -> Self {
+        let claimed = Arc::new(AtomicI64::new(0));
+        let done = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::sync_channel(threads);
+        for _ in 0..threads {
+            let (door, claimed, done) = (door.clone(), claimed.clone(), done.clone());
+            let sender = sender.clone();
+            thread::spawn(move || {
+                let mut hasher = Md5::new();
+                let mut suffix_buf = String::new();
+                while !done.load(Ordering::Relaxed) {
+                    let chunk = claimed.fetch_add(1, Ordering::Relaxed);
+                    let first = start + chunk * CHUNK_SIZE;
+                    let hits = (first..first + CHUNK_SIZE)
+                        .filter_map(|suffix| {
+                            zero_hash(&door, &mut hasher, &mut suffix_buf, suffix, zeros)
+                                .map(|digest| (suffix, digest))
+                        })
+                        .collect::<Vec<_>>();
+                    if sender.send((chunk, hits)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        Workers {
+            receiver,
+            done,
+            finished: BTreeMap::new(),
+            next_chunk: 0,
+            current: Vec::new().into_iter(),
+        }
+    }
+
+    fn next(&mut self) -> Option<Hit> {
+        loop {
+            if let Some(hit) = self.current.next() {
+                return Some(hit);
+            }
+            if let Some(hits) = self.finished.remove(&self.next_chunk) {
+                self.next_chunk += 1;
+                self.current = hits.into_iter();
+                continue;
+            }
+            let (chunk, hits) = self.receiver.recv().ok()?;
+            self.finished.insert(chunk, hits);
+        }
+    }
+}
+
+impl Drop for Workers {
+    /// Stop the workers. Any which are waiting to send a chunk stop
+    /// when the receiver is dropped, and the rest stop after the chunk
+    /// they are working on.
+    fn drop(&mut self) {
+        self.done.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The hits found for a door so far, so they can be read more than
+/// once without hashing again.
+///
+/// Hits can be saved to a file in a cache directory, keyed by door ID,
+/// number of zeros and starting suffix, and loaded back on later runs.
+pub struct HitCache {
+    hits: Vec<Hit>,
+    /// The suffix the first hit was searched for from.
+    start: i64,
+    stream: ZeroHashStream,
+}
+
+impl HitCache {
+    /// Create an empty cache, which finds hits with the given stream.
+    pub fn new(stream: ZeroHashStream) -> Self {
+        HitCache {
+            hits: Vec::new(),
+            start: stream.start,
+            stream,
+        }
+    }
+
+    /// Load the hits saved in a cache directory for the stream's door,
+    /// if there are any. More hits are found with the stream, starting
+    /// after the last one loaded.
+    pub fn load<P: AsRef<Path>>(stream: ZeroHashStream, dir: P) -> Result<Self, ReadError> {
+        let path = Self::path(&stream, stream.start, dir.as_ref());
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::new(stream)),
+            Err(e) => return Err(e.into()),
+        };
+        let mut hits = Vec::<Hit>::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let hit = parse_hit(&line?).map_err(|e| e.at_line(i + 1))?;
+            if let Some(&(last, _)) = hits.last() {
+                if hit.0 <= last {
+                    let expected = format!("a suffix larger than {}", last);
+                    return Err(ParseError::unexpected(1, expected, hit.0.to_string())
+                        .at_line(i + 1)
+                        .into());
+                }
             }
+            hits.push(hit);
+        }
+        let start = stream.start;
+        let resume = hits.last().map_or(start, |&(suffix, _)| suffix + 1);
+        Ok(HitCache {
+            hits,
+            start,
+            stream: stream.starting_at(resume),
         })
-        .filter_map(|hash| hash.chars().nth(5))
+    }
+
+    /// Save the hits found so far to a cache directory, creating it if
+    /// needed.
+    ///
+    /// The hits are written to a temporary file which then replaces
+    /// the cache file, so a run which stops part way through never
+    /// leaves a truncated cache behind.
+    pub fn save<P: AsRef<Path>>(&self, dir: P) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let path = Self::path(&self.stream, self.start, dir);
+        let mut temp_name = path.file_name().expect("cache paths have file names").to_owned();
+        temp_name.push(format!(".{}.tmp", process::id()));
+        let temp = dir.join(temp_name);
+        let result = self.write_hits(&temp).and_then(|_| fs::rename(&temp, &path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        result
+    }
+
+    /// Write the hits found so far to a file.
+    fn write_hits(&self, path: &Path) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        for &(suffix, ref digest) in &self.hits {
+            writeln!(file, "{} {}", suffix, to_hex(digest))?;
+        }
+        file.flush()
+    }
+
+    /// Get the path of the cache file for a stream's door and zeros,
+    /// with hits searched for from `start`. Door IDs are escaped so
+    /// they are always safe to use in a file name.
+    fn path(stream: &ZeroHashStream, start: i64, dir: &Path) -> PathBuf {
+        let mut name = String::from("day5-");
+        for b in stream.door().bytes() {
+            if b.is_ascii_alphanumeric() {
+                name.push(b as char);
+            } else {
+                write!(name, "%{:02x}", b).expect("writing to a string can't fail");
+            }
+        }
+        write!(name, "-{}", stream.zeros()).expect("writing to a string can't fail");
+        if start != 0 {
+            write!(name, "-from-{}", start).expect("writing to a string can't fail");
+        }
+        name.push_str(".txt");
+        dir.join(name)
+    }
+
+    /// Get the number of zeros hashes must start with.
+    pub fn zeros(&self) -> usize {
+        self.stream.zeros()
+    }
+
+    /// Iterate over the hits, starting with the ones already found.
+    pub fn hits(&mut self) -> Hits<'_> {
+        Hits {
+            cache: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over the hits in a `HitCache`.
+pub struct Hits<'a> {
+    cache: &'a mut HitCache,
+    index: usize,
+}
+
+impl<'a> Iterator for Hits<'a> {
+    type Item = Hit;
+
+    fn next(&mut self) -> Option<Hit> {
+        if self.index == self.cache.hits.len() {
+            let hit = self.cache.stream.next()?;
+            self.cache.hits.push(hit);
+        }
+        self.index += 1;
+        Some(self.cache.hits[self.index - 1])
+    }
+}
+
+/// Parse a hit saved in a cache file, a suffix and a hex digest.
+fn parse_hit(line: &str) -> Result<Hit, ParseError> {
+    let (suffix, rest) = read_number::<i64>(line, 1)?;
+    let column = column_at(line, rest);
+    let hex = expect_str(rest, column, " ")?;
+    let column = column + 1;
+    if hex.len() != 32 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ParseError::unexpected(column, "32 hex digits", hex));
+    }
+    let mut digest = [0; 16];
+    for (i, byte) in digest.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).expect("digits are checked above");
+    }
+    Ok((suffix, digest))
+}
+
+/// Build the password from the digit after the zeros in each hit.
+pub fn password_from<I>(hits: I, zeros: usize) -> String
+    where I: IntoIterator<Item = Hit>
+{
+    hits.into_iter()
         .take(8)
+        .map(|(_, digest)| {
+            char::from_digit(nibble(&digest, zeros) as u32, 16).expect("nibbles are hex digits")
+        })
         .collect()
 }
 
-pub fn get_indexed_password(door: &str) -> String {
-    let mut result = vec!['_', '_', '_', '_', '_', '_', '_', '_'];
-    let mut hasher = Md5::new();
+/// Build the password from the position and digit after the zeros in
+/// each hit. Only the first digit for each position is used.
+pub fn indexed_password_from<I>(hits: I, zeros: usize) -> String
+    where I: IntoIterator<Item = Hit>
+{
+    let mut result = vec!['_'; 8];
     let mut remaining = 8;
-    for hash in (0..).filter_map(|i| {
-        let hash = hash_for_suffix(door, &mut hasher, i);
-        if hash.starts_with("00000") {
-            Some(hash)
-        } else {
-            None
-        }
-    }) {
-        let i = hash.chars().nth(5).unwrap() as usize - '0' as usize;
+    for (_, digest) in hits {
+        let i = nibble(&digest, zeros) as usize;
         if i < 8 && result[i] == '_' {
-            result[i] = hash.chars().nth(6).unwrap();
+            result[i] = char::from_digit(nibble(&digest, zeros + 1) as u32, 16)
+                .expect("nibbles are hex digits");
             remaining -= 1;
             if remaining == 0 {
                 break;
@@ -52,6 +415,48 @@ pub fn get_indexed_password(door: &str) -> String {
     result.into_iter().collect()
 }
 
+pub fn get_password(door: &str) -> String {
+    get_password_with_threads(door, default_threads())
+}
+
+/// Find the password, searching with a given number of threads.
+pub fn get_password_with_threads(door: &str, threads: usize) -> String {
+    password_from(ZeroHashStream::new(door).with_threads(threads), ZEROS)
+}
+
+pub fn get_indexed_password(door: &str) -> String {
+    get_indexed_password_with_threads(door, default_threads())
+}
+
+/// Find the indexed password, searching with a given number of
+/// threads.
+pub fn get_indexed_password_with_threads(door: &str, threads: usize) -> String {
+    indexed_password_from(ZeroHashStream::new(door).with_threads(threads), ZEROS)
+}
+
+/// The ID of the door to find the password for. Hits are shared
+/// between the two parts, so the second part only has to search past
+/// where the first part stopped.
+pub struct Door(RefCell<HitCache>);
+
+impl Solution for Door {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Door(RefCell::new(HitCache::new(ZeroHashStream::new(input.trim())))))
+    }
+
+    fn part1(&self) -> Box<dyn fmt::Display> {
+        let mut cache = self.0.borrow_mut();
+        let zeros = cache.zeros();
+        Box::new(password_from(cache.hits(), zeros))
+    }
+
+    fn part2(&self) -> Box<dyn fmt::Display> {
+        let mut cache = self.0.borrow_mut();
+        let zeros = cache.zeros();
+        Box::new(indexed_password_from(cache.hits(), zeros))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -59,6 +464,9 @@ mod test {
 
     use super::crypto::md5::Md5;
 
+    use std::env;
+    use std::process;
+
     #[test]
     fn char_at() {
         assert_eq!(Some('2'), "124".chars().nth(1));
@@ -72,6 +480,164 @@ mod test {
         assert!(hash_for_suffix("abc", &mut hasher, 5017308).starts_with("000008f82"));
     }
 
+    #[test]
+    fn zero_hashes() {
+        let mut hasher = Md5::new();
+        let mut buf = String::new();
+        let digest = zero_hash("abc", &mut hasher, &mut buf, 3231929, 5).unwrap();
+        assert_eq!(hash_for_suffix("abc", &mut hasher, 3231929), to_hex(&digest));
+        assert_eq!(1, nibble(&digest, 5));
+        assert_eq!(None, zero_hash("abc", &mut hasher, &mut buf, 3231928, 5));
+        assert!(zero_hash("abc", &mut hasher, &mut buf, 5017308, 6).is_none());
+        assert!(zero_hash("abc", &mut hasher, &mut buf, 5017308, 5).is_some());
+    }
+
+    /// Search one suffix at a time, on a single thread.
+    fn sequential_hashes(door: &str, zeros: usize, count: usize) -> Vec<(i64, String)> {
+        let mut hasher = Md5::new();
+        let prefix = "0".repeat(zeros);
+        (0..)
+            .map(|i| (i, hash_for_suffix(door, &mut hasher, i)))
+            .filter(|(_, hash)| hash.starts_with(&prefix))
+            .take(count)
+            .collect()
+    }
+
+    fn stream(threads: usize) -> ZeroHashStream {
+        ZeroHashStream::new("abc").with_zeros(2).with_threads(threads)
+    }
+
+    #[test]
+    fn stream_in_order() {
+        let expected = sequential_hashes("abc", 2, 100);
+        for threads in 1..5 {
+            let hits = stream(threads)
+                .take(expected.len())
+                .map(|(suffix, digest)| (suffix, to_hex(&digest)))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, hits);
+        }
+        let (suffix, _) = expected[50];
+        assert_eq!(&expected[50..60],
+                   &stream(2)
+                       .starting_at(suffix)
+                       .take(10)
+                       .map(|(suffix, digest)| (suffix, to_hex(&digest)))
+                       .collect::<Vec<_>>()[..]);
+    }
+
+    #[test]
+    fn passwords_match_across_threads() {
+        let expected = password_from(stream(1), 2);
+        assert_eq!(8, expected.len());
+        let indexed = indexed_password_from(stream(1), 2);
+        assert!(!indexed.contains('_'));
+        for threads in 2..5 {
+            assert_eq!(expected, password_from(stream(threads), 2));
+            assert_eq!(indexed, indexed_password_from(stream(threads), 2));
+        }
+    }
+
+    #[test]
+    fn share_hits() {
+        let mut cache = HitCache::new(stream(2));
+        let password = password_from(cache.hits(), 2);
+        let found = cache.hits.len();
+        assert_eq!(8, found);
+        assert_eq!(password_from(stream(1), 2), password);
+        let indexed = indexed_password_from(cache.hits(), 2);
+        assert_eq!(indexed_password_from(stream(1), 2), indexed);
+        assert!(cache.hits.len() >= found);
+        assert_eq!(password, password_from(cache.hits(), 2));
+    }
+
+    #[test]
+    fn cache_hits_in_files() {
+        let dir = env::temp_dir().join(format!("advent-day5-{}", process::id()));
+        let mut cache = HitCache::new(stream(2));
+        let indexed = indexed_password_from(cache.hits(), 2);
+        cache.save(&dir).unwrap();
+        assert!(dir.join("day5-abc-2.txt").exists());
+
+        let mut loaded = HitCache::load(stream(2), &dir).unwrap();
+        assert_eq!(cache.hits, loaded.hits);
+        let (last, _) = *cache.hits.last().unwrap();
+        assert_eq!(last + 1, loaded.stream.start);
+        assert_eq!(indexed, indexed_password_from(loaded.hits(), 2));
+        let more = loaded.hits()
+            .take(cache.hits.len() + 5)
+            .map(|(suffix, digest)| (suffix, to_hex(&digest)))
+            .collect::<Vec<_>>();
+        assert_eq!(sequential_hashes("abc", 2, more.len()), more);
+
+        let empty = HitCache::load(stream(2).with_zeros(3), &dir).unwrap();
+        assert!(empty.hits.is_empty());
+
+        loaded.save(&dir).unwrap();
+        let files = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["day5-abc-2.txt"], files);
+        assert_eq!(loaded.hits, HitCache::load(stream(2), &dir).unwrap().hits);
+
+        let mut later = HitCache::new(stream(2).starting_at(last + 1));
+        later.hits().take(3).count();
+        later.save(&dir).unwrap();
+        assert!(dir.join(format!("day5-abc-2-from-{}.txt", last + 1)).exists());
+        assert_eq!(loaded.hits, HitCache::load(stream(2), &dir).unwrap().hits);
+        let reloaded = HitCache::load(stream(2).starting_at(last + 1), &dir).unwrap();
+        assert_eq!(later.hits, reloaded.hits);
+
+        fs::write(dir.join("day5-abc-2.txt"), "12 00ab\n").unwrap();
+        match HitCache::load(stream(2), &dir) {
+            Err(ReadError::Parse(err)) => {
+                assert_eq!(ParseError::unexpected(4, "32 hex digits", "00ab"), err)
+            }
+            _ => panic!("expected a parse error"),
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn zeros_leave_room_for_passwords() {
+        assert_eq!(MAX_ZEROS, ZeroHashStream::new("abc").with_zeros(MAX_ZEROS).zeros());
+        let digest = [0; 16];
+        assert_eq!("00000000", password_from(vec![(0, digest); 8], MAX_ZEROS));
+        assert_eq!("0_______", indexed_password_from(vec![(0, digest); 8], MAX_ZEROS));
+    }
+
+    #[test]
+    #[should_panic(expected = "passwords need two digits after the zeros")]
+    fn too_many_zeros() {
+        ZeroHashStream::new("abc").with_zeros(MAX_ZEROS + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "searches need at least one thread")]
+    fn no_threads() {
+        ZeroHashStream::new("abc").with_threads(0);
+    }
+
+    #[test]
+    fn parse_hits() {
+        let digest = "00a1b2c3d4e5f60718293a4b5c6d7e8f";
+        let (suffix, parsed) = parse_hit(&format!("42 {}", digest)).unwrap();
+        assert_eq!(42, suffix);
+        assert_eq!(digest, to_hex(&parsed));
+        assert_eq!(Err(ParseError::unexpected_char(1, "a number", 'x')), parse_hit("x"));
+        assert_eq!(Err(ParseError::unexpected_end(3, "\" \"")), parse_hit("42"));
+    }
+
+    #[test]
+    fn cache_file_names() {
+        let stream = ZeroHashStream::new("a/b c");
+        assert_eq!(Path::new("cache").join("day5-a%2fb%20c-5.txt"),
+                   HitCache::path(&stream, 0, Path::new("cache")));
+        assert_eq!(Path::new("cache").join("day5-a%2fb%20c-5-from-100.txt"),
+                   HitCache::path(&stream, 100, Path::new("cache")));
+    }
+
     // #[test]
     // fn example_password_decrypt() {
     //     assert_eq!("18f47a30", get_password("abc"));
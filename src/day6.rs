@@ -1,6 +1,9 @@
 //! Character frequencies
 
 use std::collections::HashMap;
+use std::fmt;
+
+use super::{ParseError, Solution};
 
 pub fn recover_plaintext_by<F>(jammed: &str, char_chooser: F) -> String
     where F: Fn(HashMap<char, usize>) -> char
@@ -8,7 +11,7 @@ pub fn recover_plaintext_by<F>(jammed: &str, char_chooser: F) -> String
     let mut chars = jammed.lines()
         .fold(HashMap::new(), |mut counts, line| {
             for (i, c) in line.chars().enumerate() {
-                let char_counts = counts.entry(i).or_insert_with(|| HashMap::new());
+                let char_counts = counts.entry(i).or_insert_with(HashMap::new);
                 *char_counts.entry(c).or_insert(0) += 1;
             }
             counts
@@ -40,10 +43,27 @@ pub fn recover_common_plaintext(jammed: &str) -> String {
     })
 }
 
+/// The repeated, jammed, message.
+pub struct Jammed(String);
+
+impl Solution for Jammed {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Jammed(input.to_string()))
+    }
+
+    fn part1(&self) -> Box<dyn fmt::Display> {
+        Box::new(recover_common_plaintext(&self.0))
+    }
+
+    fn part2(&self) -> Box<dyn fmt::Display> {
+        Box::new(recover_uncommon_plaintext(&self.0))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
-    const EXAMPLE_INPUT: &'static str = "eedadn
+    const EXAMPLE_INPUT: &str = "eedadn
 drvtee
 eandsr
 raavrd
@@ -71,4 +91,11 @@ enarar";
     fn example_less_common_decoded() {
         assert_eq!("advent", recover_uncommon_plaintext(EXAMPLE_INPUT));
     }
+
+    #[test]
+    fn solve_example() {
+        let jammed = Jammed::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!("easter", jammed.part1().to_string());
+        assert_eq!("advent", jammed.part2().to_string());
+    }
 }
@@ -1,5 +1,10 @@
 //! IPV7 Parsing
 
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::{ParseError, Solution};
+
 pub struct Ip<'a> {
     parts: Vec<IpPart<'a>>,
 }
@@ -29,20 +34,17 @@ fn is_aba(s: &[u8]) -> bool {
 }
 
 fn to_bab(s: &[u8]) -> Vec<u8> {
-    let mut bab = Vec::new();
-    bab.push(s[1]);
-    bab.push(s[0]);
-    bab.push(s[1]);
-    bab
+    vec![s[1], s[0], s[1]]
 }
 
 /// Parse a IP Parts from a string.
-fn ip_parts_from_str<'a>(s: &'a str) -> Vec<IpPart<'a>> {
+fn ip_parts_from_str(s: &str) -> Result<Vec<IpPart<'_>>, ParseError> {
     use self::ParseState::*;
     let mut parts = Vec::new();
     let mut state = Start;
     let mut start = 0;
-    for (pos, ch) in s.char_indices() {
+    for (index, (pos, ch)) in s.char_indices().enumerate() {
+        let column = index + 1;
         state = match state {
             Start => {
                 match ch {
@@ -50,27 +52,27 @@ fn ip_parts_from_str<'a>(s: &'a str) -> Vec<IpPart<'a>> {
                         start = pos + 1;
                         Hypernet
                     }
-                    'a'...'z' => {
+                    'a'..='z' => {
                         start = pos;
                         Standard
                     }
-                    _ => panic!("invalid character {} at {}", ch, pos),
+                    _ => return Err(ParseError::unexpected_char(column, "a letter or '['", ch)),
                 }
             }
             Standard => {
                 match ch {
-                    'a'...'z' => Standard,
+                    'a'..='z' => Standard,
                     '[' => {
                         parts.push(IpPart::Standard(&s[start..pos]));
                         start = pos + 1;
                         Hypernet
                     }
-                    _ => panic!("invalid character {} at {}", ch, pos),
+                    _ => return Err(ParseError::unexpected_char(column, "a letter or '['", ch)),
                 }
             }
             Hypernet => {
                 match ch {
-                    'a'...'z' => {
+                    'a'..='z' => {
                         if start == 0 {
                             start = pos
                         }
@@ -80,7 +82,7 @@ fn ip_parts_from_str<'a>(s: &'a str) -> Vec<IpPart<'a>> {
                         parts.push(IpPart::Hypernet(&s[start..pos]));
                         Start
                     }
-                    _ => panic!("invalid character {} at {}", ch, pos),
+                    _ => return Err(ParseError::unexpected_char(column, "a letter or ']'", ch)),
                 }
             }
         };
@@ -90,15 +92,17 @@ fn ip_parts_from_str<'a>(s: &'a str) -> Vec<IpPart<'a>> {
             parts.push(IpPart::Standard(&s[start..]));
         }
         Start => (),
-        Hypernet => panic!("Unterminated hypernet block starting at {}", start),
+        Hypernet => return Err(ParseError::unexpected_end(s.chars().count() + 1, "']'")),
     };
-    parts
+    Ok(parts)
 }
 
-impl<'a> From<&'a str> for Ip<'a> {
+impl<'a> TryFrom<&'a str> for Ip<'a> {
+    type Error = ParseError;
+
     /// Parse an IP Packet from a string.
-    fn from(s: &'a str) -> Self {
-        Ip { parts: ip_parts_from_str(s) }
+    fn try_from(s: &'a str) -> Result<Self, ParseError> {
+        Ok(Ip { parts: ip_parts_from_str(s)? })
     }
 }
 
@@ -110,12 +114,12 @@ impl<'a> Ip<'a> {
         if self.parts
             .iter()
             .filter_map(|part| {
-                match part {
-                    &IpPart::Standard(_) => None,
-                    &IpPart::Hypernet(hyp) => Some(hyp),
+                match *part {
+                    IpPart::Standard(_) => None,
+                    IpPart::Hypernet(hyp) => Some(hyp),
                 }
             })
-            .any(|part| has_abba(part)) {
+            .any(has_abba) {
             return false;
         }
 
@@ -124,20 +128,20 @@ impl<'a> Ip<'a> {
         self.parts
             .iter()
             .filter_map(|part| {
-                match part {
-                    &IpPart::Standard(std) => Some(std),
-                    &IpPart::Hypernet(_) => None,
+                match *part {
+                    IpPart::Standard(std) => Some(std),
+                    IpPart::Hypernet(_) => None,
                 }
             })
-            .any(|part| has_abba(part))
+            .any(has_abba)
     }
 
     pub fn supports_ssl(&self) -> bool {
         let mut abas: Vec<&[u8]> = Vec::new();
         let mut babs: Vec<&[u8]> = Vec::new();
         for part in self.parts.iter() {
-            match part {
-                &IpPart::Standard(std) => {
+            match *part {
+                IpPart::Standard(std) => {
                     for s in std.as_bytes().windows(3).filter(|window| is_aba(window)) {
                         if babs.contains(&&(to_bab(s)[..])) {
                             return true;
@@ -145,7 +149,7 @@ impl<'a> Ip<'a> {
                         abas.push(s);
                     }
                 }
-                &IpPart::Hypernet(hyper) => {
+                IpPart::Hypernet(hyper) => {
                     for s in hyper.as_bytes().windows(3).filter(|window| is_aba(window)) {
                         if abas.contains(&&(to_bab(s)[..])) {
                             return true;
@@ -159,14 +163,51 @@ impl<'a> Ip<'a> {
     }
 }
 
+/// A list of IPv7 addresses.
+pub struct Addresses(String);
+
+impl Addresses {
+    /// Count the addresses which match a given predicate.
+    fn count_by<F>(&self, predicate: F) -> usize
+        where F: Fn(&Ip) -> bool
+    {
+        self.0
+            .lines()
+            .map(|line| Ip::try_from(line).expect("addresses are checked when parsed"))
+            .filter(|ip| predicate(ip))
+            .count()
+    }
+}
+
+impl Solution for Addresses {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        for (i, line) in input.lines().enumerate() {
+            Ip::try_from(line).map_err(|e| e.at_line(i + 1))?;
+        }
+        Ok(Addresses(input.to_string()))
+    }
+
+    fn part1(&self) -> Box<dyn fmt::Display> {
+        Box::new(self.count_by(|ip| ip.supports_tls()))
+    }
+
+    fn part2(&self) -> Box<dyn fmt::Display> {
+        Box::new(self.count_by(|ip| ip.supports_ssl()))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use super::*;
 
+    fn ip(s: &str) -> Ip<'_> {
+        Ip::try_from(s).unwrap()
+    }
+
     macro_rules! check_parse {
         ($ip:expr, $parts:expr) => {
-            let ip = Ip::from($ip);
+            let ip = Ip::try_from($ip).unwrap();
             assert_eq!($parts, ip.parts);
         }
     }
@@ -201,17 +242,39 @@ mod test {
 
     #[test]
     fn example_tls_support() {
-        assert_eq!(true, Ip::from("ioxxoj[asdfgh]zxcvbn").supports_tls());
-        assert_eq!(false, Ip::from("aaaa[qwer]tyui").supports_tls());
-        assert_eq!(false, Ip::from("abcd[bddb]xyyx").supports_tls());
-        assert_eq!(true, Ip::from("abba[mnop]qrst").supports_tls());
+        assert!(ip("ioxxoj[asdfgh]zxcvbn").supports_tls());
+        assert!(!ip("aaaa[qwer]tyui").supports_tls());
+        assert!(!ip("abcd[bddb]xyyx").supports_tls());
+        assert!(ip("abba[mnop]qrst").supports_tls());
     }
 
     #[test]
     fn example_ssl_support() {
-        assert_eq!(true, Ip::from("aba[bab]xyz").supports_ssl());
-        assert_eq!(false, Ip::from("xyx[xyx]xyx").supports_ssl());
-        assert_eq!(true, Ip::from("aaa[kek]eke").supports_ssl());
-        assert_eq!(true, Ip::from("zazbz[bzb]cdb").supports_ssl());
+        assert!(ip("aba[bab]xyz").supports_ssl());
+        assert!(!ip("xyx[xyx]xyx").supports_ssl());
+        assert!(ip("aaa[kek]eke").supports_ssl());
+        assert!(ip("zazbz[bzb]cdb").supports_ssl());
+    }
+
+    #[test]
+    fn solve_addresses() {
+        let addresses = Addresses::parse("abba[mnop]qrst
+abcd[bddb]xyyx
+aba[bab]xyz
+aaa[kek]eke")
+            .unwrap();
+        assert_eq!("1", addresses.part1().to_string());
+        assert_eq!("2", addresses.part2().to_string());
+    }
+
+    #[test]
+    fn parse_invalid_addresses() {
+        assert_eq!(Err(ParseError::unexpected_char(5, "a letter or '['", 'X')),
+                   ip_parts_from_str("abcdX"));
+        assert_eq!(Err(ParseError::unexpected_char(4, "a letter or ']'", '[')),
+                   ip_parts_from_str("a[b[c]"));
+        assert_eq!(Err(ParseError::unexpected_end(9, "']'")),
+                   ip_parts_from_str("abba[xyz"));
+        assert_eq!(2, Addresses::parse("abba[mnop]qrst\nab]cd").err().unwrap().line());
     }
 }
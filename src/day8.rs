@@ -1,13 +1,13 @@
-extern crate onig;
-
 use std::fmt;
+use std::str::FromStr;
 
-use self::onig::*;
+use super::{ParseError, Solution};
+use error::{column_at, expect_str, parse_number, read_number};
 
 /// A display command
 trait Command {
     /// Apply a command to the display.
-    fn apply(&self, display: &mut PixelBuffer);
+    fn apply(&self, display: &mut dyn PixelBuffer);
 }
 
 /// A pixel buffer
@@ -37,38 +37,50 @@ struct RotateColCommand(usize, usize);
 #[derive(Debug, PartialEq)]
 struct RotateRowCommand(usize, usize);
 
-impl<'a> From<&'a str> for RectCommand {
-    fn from(s: &'a str) -> Self {
-        let re = Regex::new(r"(\d+)x(\d+)").unwrap();
-        let caps = re.captures(s).expect("not a rect command!");
-        let x = caps.at(1).unwrap().parse().unwrap();
-        let y = caps.at(2).unwrap().parse().unwrap();
-        RectCommand(x, y)
+/// Parse a pair of numbers from a command's arguments.
+///
+/// # Arguments
+///  * `s` - The arguments to parse, e.g. `column x=1 by 2`
+///  * `prefix` - The text expected before the first number
+///  * `separator` - The text expected between the two numbers
+fn parse_pair(s: &str, prefix: &str, separator: &str) -> Result<(usize, usize), ParseError> {
+    let rest = expect_str(s, 1, prefix)?;
+    let (first, rest) = read_number(rest, 1 + prefix.chars().count())?;
+    let column = column_at(s, rest);
+    let rest = expect_str(rest, column, separator)?;
+    let second = parse_number(rest, column + separator.chars().count())?;
+    Ok((first, second))
+}
+
+impl FromStr for RectCommand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (x, y) = parse_pair(s, "", "x")?;
+        Ok(RectCommand(x, y))
     }
 }
 
-impl<'a> From<&'a str> for RotateColCommand {
-    fn from(s: &'a str) -> Self {
-        let re = Regex::new(r"column x=(\d+) by (\d+)").unwrap();
-        let caps = re.captures(s).expect("not a rotate col command!");
-        let x = caps.at(1).unwrap().parse().unwrap();
-        let dist = caps.at(2).unwrap().parse().unwrap();
-        RotateColCommand(x, dist)
+impl FromStr for RotateColCommand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (x, dist) = parse_pair(s, "column x=", " by ")?;
+        Ok(RotateColCommand(x, dist))
     }
 }
 
-impl<'a> From<&'a str> for RotateRowCommand {
-    fn from(s: &'a str) -> Self {
-        let re = Regex::new(r"row y=(\d+) by (\d+)").unwrap();
-        let caps = re.captures(s).expect("not a rotate col command!");
-        let y = caps.at(1).unwrap().parse().unwrap();
-        let dist = caps.at(2).unwrap().parse().unwrap();
-        RotateRowCommand(y, dist)
+impl FromStr for RotateRowCommand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (y, dist) = parse_pair(s, "row y=", " by ")?;
+        Ok(RotateRowCommand(y, dist))
     }
 }
 
 impl Command for RectCommand {
-    fn apply(&self, buffer: &mut PixelBuffer) {
+    fn apply(&self, buffer: &mut dyn PixelBuffer) {
         for x in 0..self.0 {
             for y in 0..self.1 {
                 buffer.set(x, y, true);
@@ -78,7 +90,7 @@ impl Command for RectCommand {
 }
 
 impl Command for RotateColCommand {
-    fn apply(&self, buffer: &mut PixelBuffer) {
+    fn apply(&self, buffer: &mut dyn PixelBuffer) {
         let dist = self.1 % buffer.height();
         for _ in 0..dist {
             let mut px = buffer.get(self.0, 0);
@@ -93,7 +105,7 @@ impl Command for RotateColCommand {
 }
 
 impl Command for RotateRowCommand {
-    fn apply(&self, buffer: &mut PixelBuffer) {
+    fn apply(&self, buffer: &mut dyn PixelBuffer) {
         let dist = self.1 % buffer.width();
         for _ in 0..dist {
             let mut px = buffer.get(0, self.0);
@@ -156,9 +168,9 @@ impl Display {
         let mut pixels = Vec::new();
         pixels.resize(width * height, false);
         Display {
-            width: width,
-            height: height,
-            pixels: pixels,
+            width,
+            height,
+            pixels,
         }
     }
 
@@ -168,26 +180,43 @@ impl Display {
     /// # Arguments
     ///  * `cmd` - The command to run
     ///
-    /// # Pancis
+    /// # Errors
     ///
-    /// If `cmd` is not a valid command this function will panic.
-    pub fn command_str(&mut self, cmd: &str) {
-        let sp = cmd.find(" ").expect("invalid command");
+    /// If `cmd` is not a valid command, or refers to pixels outside
+    /// the display, an error is returned and the display is left
+    /// unchanged.
+    pub fn command_str(&mut self, cmd: &str) -> Result<(), ParseError> {
+        let sp = cmd.find(' ').unwrap_or(cmd.len());
         let (command, args) = cmd.split_at(sp);
-        let args = &args[1..];
+        let column = column_at(cmd, args);
+        let args = expect_str(args, column, " ")?;
+        let parse_err = |e: ParseError| e.offset_by(column);
+        let out_of_range = |expected: &str| ParseError::unexpected(column + 1, expected, args);
         match command {
-            "rect" => self.command(RectCommand::from(args)),
+            "rect" => {
+                let rect = args.parse::<RectCommand>().map_err(parse_err)?;
+                if rect.0 > self.width || rect.1 > self.height {
+                    return Err(out_of_range("a rectangle which fits on the display"));
+                }
+                self.command(rect);
+            }
+            "rotate" if args.starts_with("row") => {
+                let rotate = args.parse::<RotateRowCommand>().map_err(parse_err)?;
+                if rotate.0 >= self.height {
+                    return Err(out_of_range("a row on the display"));
+                }
+                self.command(rotate);
+            }
             "rotate" => {
-                let sp = args.find(" ").expect("invalid rotate args");
-                let (ty, _)= args.split_at(sp);
-                match ty {
-                    "row" => self.command(RotateRowCommand::from(args)),
-                    "column" => self.command(RotateColCommand::from(args)),
-                    _ => panic!("invalid roration {} ({})", ty, args),
+                let rotate = args.parse::<RotateColCommand>().map_err(parse_err)?;
+                if rotate.0 >= self.width {
+                    return Err(out_of_range("a column on the display"));
                 }
-            },
-            _ => panic!("unknown comamnd!"),
+                self.command(rotate);
+            }
+            _ => return Err(ParseError::unexpected(1, "'rect' or 'rotate'", command)),
         }
+        Ok(())
     }
 
     /// Run a command on the display. Allows the command to operate on
@@ -208,6 +237,27 @@ impl Display {
     }
 }
 
+/// The screen after running all of the commands.
+pub struct Screen(Display);
+
+impl Solution for Screen {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut display = Display::default();
+        for (i, cmd) in input.lines().enumerate() {
+            display.command_str(cmd).map_err(|e| e.at_line(i + 1))?;
+        }
+        Ok(Screen(display))
+    }
+
+    fn part1(&self) -> Box<dyn fmt::Display> {
+        Box::new(self.0.active_pixels())
+    }
+
+    fn part2(&self) -> Box<dyn fmt::Display> {
+        Box::new(self.0.to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -264,30 +314,56 @@ mod test {
 
     #[test]
     fn parse_commands() {
-        assert_eq!(RectCommand(3, 2), RectCommand::from("3x2"));
-        assert_eq!(RotateColCommand(1, 1), RotateColCommand::from("column x=1 by 1"));
-        assert_eq!(RotateRowCommand(0, 4), RotateRowCommand::from("row y=0 by 4"));
+        assert_eq!(Ok(RectCommand(3, 2)), "3x2".parse());
+        assert_eq!(Ok(RotateColCommand(1, 1)), "column x=1 by 1".parse());
+        assert_eq!(Ok(RotateRowCommand(0, 4)), "row y=0 by 4".parse());
+    }
+
+    #[test]
+    fn parse_invalid_commands() {
+        assert_eq!(Err(ParseError::unexpected_end(2, "\"x\"")),
+                   "3".parse::<RectCommand>());
+        assert_eq!(Err(ParseError::unexpected_char(10, "a number", 'y')),
+                   "column x=y by 1".parse::<RotateColCommand>());
+        assert_eq!(Err(ParseError::unexpected_char(11, "\" by \"", 'e')),
+                   "row y=0 bye 4".parse::<RotateRowCommand>());
+    }
+
+    #[test]
+    fn display_rejects_invalid_command_strings() {
+        let mut display = Display::new(7, 3);
+        assert_eq!(Err(ParseError::unexpected(1, "'rect' or 'rotate'", "fill")),
+                   display.command_str("fill 3x2"));
+        assert_eq!(Err(ParseError::unexpected_char(14, "a number", 'x')),
+                   display.command_str("rotate row y=x by 2"));
+        assert_eq!(Err(ParseError::unexpected_end(5, "\" \"")),
+                   display.command_str("rect"));
+        assert_eq!(Err(ParseError::unexpected_end(5, "\" \"")),
+                   display.command_str("réct"));
+        assert!(display.command_str("rect 8x2").is_err());
+        assert!(display.command_str("rotate column x=7 by 1").is_err());
+        assert_eq!(0, display.active_pixels());
     }
 
     #[test]
     fn display_apply_command_string() {
         let mut display = Display::new(7, 3);
-        display.command_str("rect 3x2");
+        display.command_str("rect 3x2").unwrap();
         assert_eq!("###....
 ###....
 .......",
                    display.to_string());
-        display.command_str("rotate column x=1 by 1");
+        display.command_str("rotate column x=1 by 1").unwrap();
         assert_eq!("#.#....
 ###....
 .#.....",
                    display.to_string());
-        display.command_str("rotate row y=0 by 4");
+        display.command_str("rotate row y=0 by 4").unwrap();
         assert_eq!("....#.#
 ###....
 .#.....",
                    display.to_string());
-        display.command_str("rotate column x=1 by 1");
+        display.command_str("rotate column x=1 by 1").unwrap();
         assert_eq!(".#..#.#
 #.#....
 .#.....",
@@ -298,9 +374,16 @@ mod test {
     fn display_get_active_pixels() {
         let mut display = Display::default();
         assert_eq!(0, display.active_pixels());
-        display.command_str("rect 4x5");
+        display.command_str("rect 4x5").unwrap();
         assert_eq!(20, display.active_pixels());
-        display.command_str("rect 20x2");
+        display.command_str("rect 20x2").unwrap();
         assert_eq!(52, display.active_pixels());
     }
+
+    #[test]
+    fn solve_screen() {
+        let screen = Screen::parse("rect 3x2\nrotate column x=1 by 1").unwrap();
+        assert_eq!("6", screen.part1().to_string());
+        assert!(screen.part2().to_string().starts_with("#.#...."));
+    }
 }
@@ -1,5 +1,9 @@
 //! Decrompression of strings.
 
+use std::fmt;
+
+use super::{ParseError, Solution};
+
 /// Decompression state. This represents the current state which the
 /// decompression state machine can be in.
 enum State {
@@ -14,24 +18,37 @@ struct Window(usize, usize);
 
 macro_rules! ival {
     ($ch:expr) => {
-        ($ch as u8 - '0' as u8) as usize
+        ($ch as u8 - b'0') as usize
     }
 }
 
+/// Add a digit to the end of a number in a marker, checking for
+/// overflow.
+fn push_digit(value: usize, ch: char, column: usize) -> Result<usize, ParseError> {
+    value.checked_mul(10)
+        .and_then(|value| value.checked_add(ival!(ch)))
+        .ok_or_else(|| ParseError::unexpected_char(column, "a smaller number", ch))
+}
+
 /// Decompression function. Applies the run-length-encoding codes
 /// found within the input and returns the result.
 ///
+/// Markers count characters rather than bytes, so `(1x3)é` repeats
+/// the whole of `é`.
+///
 /// # Arugments
 ///  * `input` - The string to decode
 ///
 /// # Returns
 ///
-/// The decompressed string data.
-pub fn decompress(input: &str) -> String {
+/// The decompressed string data, or an error if a marker is invalid
+/// or its repeated data runs off the end of the input.
+pub fn decompress(input: &str) -> Result<String, ParseError> {
     use self::State::*;
     let mut res = String::with_capacity(input.len());
     let mut state = State::Start;
-    for ch in input.chars() {
+    for (col, ch) in input.chars().enumerate() {
+        let column = col + 1;
         state = match state {
             Start => match ch {
                 '(' => ReadingLength(0),
@@ -41,14 +58,15 @@ pub fn decompress(input: &str) -> String {
                 }
             },
             ReadingLength(len) => match ch {
-                '0'...'9' => ReadingLength((len * 10) + ival!(ch)),
+                '0'..='9' => ReadingLength(push_digit(len, ch, column)?),
                 'x' => ReadingCount(len, 0),
-                _ => panic!("unexpected char {} when reading repeat length", ch),
+                _ => return Err(ParseError::unexpected_char(column, "a digit or 'x'", ch)),
             },
             ReadingCount(len, count) => match ch {
-                '0'...'9' => ReadingCount(len, (count * 10) + ival!(ch)),
-                ')' => ReadingBuff(len, count, String::with_capacity(len)),
-                _ => panic!("unexpected char {} when reading repeat cont", ch),
+                '0'..='9' => ReadingCount(len, push_digit(count, ch, column)?),
+                ')' if len == 0 => Start,
+                ')' => ReadingBuff(len, count, String::new()),
+                _ => return Err(ParseError::unexpected_char(column, "a digit or ')'", ch)),
             },
             ReadingBuff(len, count, mut buf) => {
                 buf.push(ch);
@@ -63,54 +81,133 @@ pub fn decompress(input: &str) -> String {
             }
         }
     }
-    res
+    let column = input.chars().count() + 1;
+    match state {
+        Start => Ok(res),
+        ReadingLength(_) => Err(ParseError::unexpected_end(column, "a digit or 'x'")),
+        ReadingCount(..) => Err(ParseError::unexpected_end(column, "a digit or ')'")),
+        ReadingBuff(len, ..) => {
+            Err(ParseError::unexpected_end(column, format!("{} more characters", len)))
+        }
+    }
+}
+
+/// Get the length of the input after decompressing it once, in
+/// characters.
+///
+/// The length is calculated without building the decompressed output,
+/// so large repeat counts don't use any more memory. Returns an error
+/// if a marker is invalid, its repeated data runs off the end of the
+/// input, or the length is too large to represent.
+pub fn single_pass_length(input: &str) -> Result<usize, ParseError> {
+    length(input, false)
 }
 
 /// Get decompressed lenght.
 ///
-/// Given an input string return the length of the string after
-/// decompressing it and, optionally, expanding markers in the output
-/// of each level of decompression.
-pub fn decompressed_length(input: &str) -> usize {
+/// Given an input string return the length of the string, in
+/// characters, after decompressing it and expanding markers in the
+/// output of each level of decompression.
+///
+/// Returns an error if a marker is invalid, its repeated data runs
+/// off the end of the input, or the length is too large to represent.
+pub fn decompressed_length(input: &str) -> Result<usize, ParseError> {
+    length(input, true)
+}
+
+/// Calculate the decompressed length of the input. If `recursive` is
+/// set markers in repeated data are expanded too, otherwise repeated
+/// data is taken literally.
+fn length(input: &str, recursive: bool) -> Result<usize, ParseError> {
     use self::State::*;
-    let mut res = 0;
+    let too_long = |column, ch| ParseError::unexpected_char(column, "a smaller decompressed length", ch);
+    let mut res: usize = 0;
     let mut windows = Vec::new();
+    // The number of characters of repeated data still to skip over
+    // when not expanding recursively.
+    let mut literal = 0;
     let mut state = State::Start;
-    for ch in input.chars() {
-        let mut char_weight = 1;
+    for (col, ch) in input.chars().enumerate() {
+        let column = col + 1;
+        if literal > 0 {
+            literal -= 1;
+            continue;
+        }
+        let mut char_weight = Some(1usize);
         let mut new_windows = Vec::new();
         for &mut Window(size, weight) in &mut windows {
             if size > 1 {
                 new_windows.push(Window(size - 1, weight));
             }
-            char_weight = weight * char_weight;
+            char_weight = char_weight.and_then(|w| w.checked_mul(weight));
         }
         windows = new_windows;
         state = match state {
             Start => match ch {
                 '(' => ReadingLength(0),
                 _ => {
-                    res += char_weight;
+                    res = char_weight.and_then(|w| res.checked_add(w))
+                        .ok_or_else(|| too_long(column, ch))?;
                     Start
                 }
             },
             ReadingLength(len) => match ch {
-                '0'...'9' => ReadingLength((len * 10) + ival!(ch)),
+                '0'..='9' => ReadingLength(push_digit(len, ch, column)?),
                 'x' => ReadingCount(len, 0),
-                _ => panic!("unexpected char {} when reading repeat length", ch),
+                _ => return Err(ParseError::unexpected_char(column, "a digit or 'x'", ch)),
             },
             ReadingCount(len, count) => match ch {
-                '0'...'9' => ReadingCount(len, (count * 10) + ival!(ch)),
+                '0'..='9' => ReadingCount(len, push_digit(count, ch, column)?),
+                ')' if recursive => {
+                    if len > 0 {
+                        windows.push(Window(len, count));
+                    }
+                    Start
+                }
                 ')' => {
-                    windows.push(Window(len, count));
+                    res = len.checked_mul(count)
+                        .and_then(|repeated| res.checked_add(repeated))
+                        .ok_or_else(|| too_long(column, ch))?;
+                    literal = len;
                     Start
-                },
-                _ => panic!("unexpected char {} when reading repeat cont", ch),
+                }
+                _ => return Err(ParseError::unexpected_char(column, "a digit or ')'", ch)),
             },
-            _ => panic!("d"),
-        }        
+            ReadingBuff(..) => unreachable!("length is calculated without buffering"),
+        }
+    }
+    let column = input.chars().count() + 1;
+    let missing = windows.iter().map(|&Window(size, _)| size).chain(Some(literal)).max();
+    match state {
+        ReadingLength(_) => Err(ParseError::unexpected_end(column, "a digit or 'x'")),
+        ReadingCount(..) => Err(ParseError::unexpected_end(column, "a digit or ')'")),
+        _ => match missing {
+            Some(size) if size > 0 => {
+                Err(ParseError::unexpected_end(column, format!("{} more characters", size)))
+            }
+            _ => Ok(res),
+        },
+    }
+}
+
+/// A compressed file.
+pub struct Compressed(String);
+
+impl Solution for Compressed {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let compressed = input.split_whitespace().collect::<String>();
+        single_pass_length(&compressed)?;
+        decompressed_length(&compressed)?;
+        Ok(Compressed(compressed))
+    }
+
+    fn part1(&self) -> Box<dyn fmt::Display> {
+        Box::new(single_pass_length(&self.0).expect("checked when parsed"))
+    }
+
+    fn part2(&self) -> Box<dyn fmt::Display> {
+        Box::new(decompressed_length(&self.0).expect("checked when parsed"))
     }
-    res
 }
 
 #[cfg(test)]
@@ -120,19 +217,79 @@ mod test {
 
     #[test]
     fn example_decompressions() {
-        assert_eq!("ADVENT", decompress("ADVENT"));
-        assert_eq!("ABBBBBC", decompress("A(1x5)BC"));
-        assert_eq!("XYZXYZXYZ", decompress("(3x3)XYZ"));
-        assert_eq!("ABCBCDEFEFG", decompress("A(2x2)BCD(2x2)EFG"));
-        assert_eq!("(1x3)A", decompress("(6x1)(1x3)A"));
-        assert_eq!("X(3x3)ABC(3x3)ABCY", decompress("X(8x2)(3x3)ABCY"));
+        assert_eq!(Ok("ADVENT".to_string()), decompress("ADVENT"));
+        assert_eq!(Ok("ABBBBBC".to_string()), decompress("A(1x5)BC"));
+        assert_eq!(Ok("XYZXYZXYZ".to_string()), decompress("(3x3)XYZ"));
+        assert_eq!(Ok("ABCBCDEFEFG".to_string()), decompress("A(2x2)BCD(2x2)EFG"));
+        assert_eq!(Ok("(1x3)A".to_string()), decompress("(6x1)(1x3)A"));
+        assert_eq!(Ok("X(3x3)ABC(3x3)ABCY".to_string()), decompress("X(8x2)(3x3)ABCY"));
     }
 
     #[test]
     fn example_recursive_decompression() {
-        assert_eq!(20, decompressed_length("X(8x2)(3x3)ABCY"));
-        assert_eq!(9, decompressed_length("(3x3)XYZ"));
-        assert_eq!(241920, decompressed_length("(27x12)(20x12)(13x14)(7x10)(1x12)A"));
-        assert_eq!(445, decompressed_length("(25x3)(3x3)ABC(2x3)XY(5x2)PQRSTX(18x9)(3x2)TWO(5x7)SEVEN"));
+        assert_eq!(Ok(20), decompressed_length("X(8x2)(3x3)ABCY"));
+        assert_eq!(Ok(9), decompressed_length("(3x3)XYZ"));
+        assert_eq!(Ok(241920), decompressed_length("(27x12)(20x12)(13x14)(7x10)(1x12)A"));
+        assert_eq!(Ok(445), decompressed_length("(25x3)(3x3)ABC(2x3)XY(5x2)PQRSTX(18x9)(3x2)TWO(5x7)SEVEN"));
+    }
+
+    #[test]
+    fn invalid_markers() {
+        assert_eq!(Err(ParseError::unexpected_char(4, "a digit or 'x'", 'y')),
+                   decompress("A(1y5)BC"));
+        assert_eq!(Err(ParseError::unexpected_char(6, "a digit or ')'", ']')),
+                   decompressed_length("A(1x5]BC"));
+        assert_eq!(Err(ParseError::unexpected_end(6, "a digit or ')'")),
+                   decompress("A(1x5"));
+        assert_eq!(Err(ParseError::unexpected_end(8, "2 more characters")),
+                   decompress("A(3x5)B"));
+        assert_eq!(Err(ParseError::unexpected_end(8, "2 more characters")),
+                   decompressed_length("A(3x5)B"));
+        assert!(decompress("(99999999999999999999x1)A").is_err());
+        assert_eq!(Err(ParseError::unexpected_end(8, "2 more characters")),
+                   single_pass_length("A(3x5)B"));
+    }
+
+    #[test]
+    fn single_pass_lengths() {
+        for input in &["ADVENT", "A(1x5)BC", "(3x3)XYZ", "A(2x2)BCD(2x2)EFG", "(6x1)(1x3)A",
+                       "X(8x2)(3x3)ABCY", "(0x9)A", "(2x0)ABC"] {
+            assert_eq!(decompress(input).map(|s| s.chars().count()), single_pass_length(input));
+        }
+        assert_eq!(Ok(usize::MAX), single_pass_length(&format!("(1x{})A", usize::MAX)));
+    }
+
+    #[test]
+    fn non_ascii_lengths() {
+        assert_eq!(Ok("ééé".to_string()), decompress("(1x3)é"));
+        assert_eq!(Ok(3), single_pass_length("(1x3)é"));
+        assert_eq!(Ok("ñéñéb".to_string()), decompress("(2x2)ñéb"));
+        assert_eq!(Ok(5), single_pass_length("(2x2)ñéb"));
+        assert_eq!(Ok("(1x3)é(1x3)é".to_string()), decompress("(6x2)(1x3)é"));
+        assert_eq!(Ok(12), single_pass_length("(6x2)(1x3)é"));
+        assert_eq!(Ok(6), decompressed_length("(6x2)(1x3)é"));
+        assert_eq!(Err(ParseError::unexpected_end(7, "1 more characters")),
+                   decompress("(2x2)é"));
+        assert_eq!(Err(ParseError::unexpected_end(7, "1 more characters")),
+                   single_pass_length("(2x2)é"));
+        assert_eq!(Err(ParseError::unexpected_end(7, "1 more characters")),
+                   decompressed_length("(2x2)é"));
+        let compressed = Compressed::parse("(1x3)é").unwrap();
+        assert_eq!("3", compressed.part1().to_string());
+        assert_eq!("3", compressed.part2().to_string());
+    }
+
+    #[test]
+    fn overflowing_lengths() {
+        assert_eq!(Err(ParseError::unexpected_char(30, "a smaller decompressed length", 'A')),
+                   decompressed_length("(15x4294967296)(1x4294967296)A"));
+        let huge = format!("(2x{})AB", usize::MAX);
+        assert_eq!(Err(ParseError::unexpected_char(huge.len() - 2,
+                                                   "a smaller decompressed length",
+                                                   ')')),
+                   single_pass_length(&huge));
+        assert!(Compressed::parse(&format!("(1x{})A(1x2)B", usize::MAX)).is_err());
+        let big = Compressed::parse("(1x4000000000000)A").unwrap();
+        assert_eq!("4000000000000", big.part1().to_string());
     }
 }
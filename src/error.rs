@@ -0,0 +1,284 @@
+//! Parse errors
+//!
+//! Shared error type for all of the puzzle input parsers.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+/// An error encountered when parsing puzzle input.
+///
+/// Lines and columns are counted from 1, and columns count characters
+/// rather than bytes. Parsers which only see a single record report
+/// errors on line 1; callers parsing a larger input use `at_line` to
+/// place the error within it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// Found something other than what was expected.
+    Unexpected {
+        line: usize,
+        column: usize,
+        expected: String,
+        found: String,
+    },
+    /// The input ended while something was still expected.
+    UnexpectedEnd {
+        line: usize,
+        column: usize,
+        expected: String,
+    },
+}
+
+impl ParseError {
+    /// Create a new error for an unexpected token at `column`.
+    pub fn unexpected<E, F>(column: usize, expected: E, found: F) -> Self
+        where E: Into<String>,
+              F: Into<String>
+    {
+        ParseError::Unexpected {
+            line: 1,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// Create a new error for an unexpected character at `column`.
+    pub fn unexpected_char<E>(column: usize, expected: E, found: char) -> Self
+        where E: Into<String>
+    {
+        Self::unexpected(column, expected, format!("{:?}", found))
+    }
+
+    /// Create a new error for input which ended early at `column`.
+    pub fn unexpected_end<E>(column: usize, expected: E) -> Self
+        where E: Into<String>
+    {
+        ParseError::UnexpectedEnd {
+            line: 1,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// The line the error occurred on.
+    pub fn line(&self) -> usize {
+        match *self {
+            ParseError::Unexpected { line, .. } |
+            ParseError::UnexpectedEnd { line, .. } => line,
+        }
+    }
+
+    /// The column the error occurred at.
+    pub fn column(&self) -> usize {
+        match *self {
+            ParseError::Unexpected { column, .. } |
+            ParseError::UnexpectedEnd { column, .. } => column,
+        }
+    }
+
+    /// Move the error to the given line.
+    pub fn at_line(mut self, new_line: usize) -> Self {
+        match self {
+            ParseError::Unexpected { ref mut line, .. } |
+            ParseError::UnexpectedEnd { ref mut line, .. } => *line = new_line,
+        }
+        self
+    }
+
+    /// Shift the error right by a number of columns. Used when a
+    /// parser was only given part of a line.
+    pub fn offset_by(mut self, columns: usize) -> Self {
+        match self {
+            ParseError::Unexpected { ref mut column, .. } |
+            ParseError::UnexpectedEnd { ref mut column, .. } => *column += columns,
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Unexpected { line, column, ref expected, ref found } => {
+                write!(f,
+                       "line {}, column {}: expected {}, found {}",
+                       line,
+                       column,
+                       expected,
+                       found)
+            }
+            ParseError::UnexpectedEnd { line, column, ref expected } => {
+                write!(f,
+                       "line {}, column {}: expected {}, found end of input",
+                       line,
+                       column,
+                       expected)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// An error encountered when reading puzzle input from a stream.
+#[derive(Debug)]
+pub enum ReadError {
+    /// The input couldn't be read.
+    Io(io::Error),
+    /// The input was read, but couldn't be parsed.
+    Parse(ParseError),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(err: ParseError) -> Self {
+        ReadError::Parse(err)
+    }
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReadError::Io(ref err) => write!(f, "could not read input: {}", err),
+            ReadError::Parse(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ReadError::Io(ref err) => Some(err),
+            ReadError::Parse(ref err) => Some(err),
+        }
+    }
+}
+
+/// Check that a string starts with some expected text.
+///
+/// Returns the remainder of the string after the expected text. The
+/// `column` is where `s` starts within the line, and is used when
+/// reporting errors.
+pub(crate) fn expect_str<'a>(s: &'a str, column: usize, expected: &str) -> Result<&'a str, ParseError> {
+    let mut found = s.chars();
+    for (i, want) in expected.chars().enumerate() {
+        match found.next() {
+            Some(c) if c == want => (),
+            Some(c) => {
+                return Err(ParseError::unexpected_char(column + i, format!("{:?}", expected), c))
+            }
+            None => return Err(ParseError::unexpected_end(column + i, format!("{:?}", expected))),
+        }
+    }
+    Ok(found.as_str())
+}
+
+/// Get the column of the start of `rest`, a suffix of `line`.
+pub(crate) fn column_at(line: &str, rest: &str) -> usize {
+    line[..line.len() - rest.len()].chars().count() + 1
+}
+
+/// Read a decimal number from the start of a string.
+///
+/// Returns the number and the remainder of the string. The `column`
+/// is where `s` starts within the line, and is used when reporting
+/// errors.
+pub(crate) fn read_number<T: FromStr>(s: &str, column: usize) -> Result<(T, &str), ParseError> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if end == 0 {
+        return Err(match s.chars().next() {
+            Some(c) => ParseError::unexpected_char(column, "a number", c),
+            None => ParseError::unexpected_end(column, "a number"),
+        });
+    }
+    let (digits, rest) = s.split_at(end);
+    let number = digits.parse::<T>()
+        .map_err(|_| ParseError::unexpected(column, "a smaller number", digits))?;
+    Ok((number, rest))
+}
+
+/// Parse a whole string as a decimal number.
+pub(crate) fn parse_number<T: FromStr>(s: &str, column: usize) -> Result<T, ParseError> {
+    let (number, rest) = read_number(s, column)?;
+    match rest.chars().next() {
+        Some(c) => Err(ParseError::unexpected_char(column + column_at(s, rest) - 1, "a digit", c)),
+        None => Ok(number),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn display_errors() {
+        assert_eq!("line 1, column 3: expected a number, found 'x'",
+                   ParseError::unexpected_char(3, "a number", 'x').to_string());
+        assert_eq!("line 4, column 2: expected ']', found end of input",
+                   ParseError::unexpected_end(2, "']'").at_line(4).to_string());
+    }
+
+    #[test]
+    fn display_read_errors() {
+        let err = ReadError::from(ParseError::unexpected_char(3, "a number", 'x'));
+        assert_eq!("line 1, column 3: expected a number, found 'x'", err.to_string());
+        let err = ReadError::from(io::Error::other("disk on fire"));
+        assert_eq!("could not read input: disk on fire", err.to_string());
+        let source = err.source().and_then(|e| e.downcast_ref::<io::Error>()).unwrap();
+        assert_eq!(io::ErrorKind::Other, source.kind());
+        assert_eq!("disk on fire", source.to_string());
+
+        let parse = ParseError::unexpected_char(3, "a number", 'x');
+        let err = ReadError::from(parse.clone());
+        assert_eq!(Some(&parse),
+                   err.source().and_then(|e| e.downcast_ref::<ParseError>()));
+    }
+
+    #[test]
+    fn move_errors() {
+        let err = ParseError::unexpected_char(3, "a number", 'x').at_line(7).offset_by(10);
+        assert_eq!(7, err.line());
+        assert_eq!(13, err.column());
+    }
+
+    #[test]
+    fn expect_strings() {
+        assert_eq!(Ok("123"), expect_str("x=123", 1, "x="));
+        assert_eq!(Err(ParseError::unexpected_char(3, "\"x=\"", 'y')),
+                   expect_str("y=123", 3, "x="));
+        assert_eq!(Err(ParseError::unexpected_end(2, "\"x=\"")), expect_str("x", 1, "x="));
+    }
+
+    #[test]
+    fn columns_count_chars() {
+        assert_eq!(1, column_at("héllo", "héllo"));
+        assert_eq!(3, column_at("héllo", "llo"));
+        assert_eq!(6, column_at("héllo", ""));
+    }
+
+    #[test]
+    fn read_numbers() {
+        assert_eq!(Ok((123, "x4")), read_number::<u32>("123x4", 1));
+        assert_eq!(Err(ParseError::unexpected_char(5, "a number", 'x')),
+                   read_number::<u32>("x4", 5));
+        assert_eq!(Err(ParseError::unexpected_end(5, "a number")),
+                   read_number::<u32>("", 5));
+        assert_eq!(Err(ParseError::unexpected(1, "a smaller number", "300")),
+                   read_number::<u8>("300", 1));
+    }
+
+    #[test]
+    fn parse_whole_numbers() {
+        assert_eq!(Ok(42), parse_number::<i32>("42", 1));
+        assert_eq!(Err(ParseError::unexpected_char(4, "a digit", '!')),
+                   parse_number::<i32>("42!", 2));
+    }
+}
@@ -1,5 +1,9 @@
 //! Solutions to Advent of Code 2016
 
+use std::fmt;
+
+pub mod error;
+
 pub mod day1;
 pub mod day2;
 pub mod day3;
@@ -9,3 +13,126 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+
+pub use error::{ParseError, ReadError};
+
+/// A day's puzzle solution.
+///
+/// Each day's module provides a type which can be parsed from the
+/// puzzle input and then asked for the answer to either part.
+pub trait Solution {
+    /// Parse the puzzle input.
+    fn parse(input: &str) -> Result<Self, ParseError> where Self: Sized;
+
+    /// Get the answer to the first part of the puzzle.
+    fn part1(&self) -> Box<dyn fmt::Display>;
+
+    /// Get the answer to the second part of the puzzle.
+    fn part2(&self) -> Box<dyn fmt::Display>;
+}
+
+/// A registered day.
+pub struct Day {
+    /// The name of the day, e.g. `day1`.
+    pub name: &'static str,
+    /// The title of the day's puzzle.
+    pub title: &'static str,
+    parse: fn(&str) -> Result<Box<dyn Solution>, ParseError>,
+}
+
+impl Day {
+    /// Parse the puzzle input into this day's solution.
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solution>, ParseError> {
+        (self.parse)(input)
+    }
+}
+
+/// Parse a solution and box it up for the registry.
+fn boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, ParseError> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+/// All of the registered days, in order.
+pub const DAYS: &[Day] = &[Day {
+                               name: "day1",
+                               title: "No Time for a Taxicab",
+                               parse: boxed::<day1::Route<i64>>,
+                           },
+                           Day {
+                               name: "day2",
+                               title: "Bathroom Security",
+                               parse: boxed::<day2::Instructions>,
+                           },
+                           Day {
+                               name: "day3",
+                               title: "Squares With Three Sides",
+                               parse: boxed::<day3::Triangles>,
+                           },
+                           Day {
+                               name: "day4",
+                               title: "Security Through Obscurity",
+                               parse: boxed::<day4::Rooms>,
+                           },
+                           Day {
+                               name: "day5",
+                               title: "How About a Nice Game of Chess?",
+                               parse: boxed::<day5::Door>,
+                           },
+                           Day {
+                               name: "day6",
+                               title: "Signals and Noise",
+                               parse: boxed::<day6::Jammed>,
+                           },
+                           Day {
+                               name: "day7",
+                               title: "Internet Protocol Version 7",
+                               parse: boxed::<day7::Addresses>,
+                           },
+                           Day {
+                               name: "day8",
+                               title: "Two-Factor Authentication",
+                               parse: boxed::<day8::Screen>,
+                           },
+                           Day {
+                               name: "day9",
+                               title: "Explosives in Cyberspace",
+                               parse: boxed::<day9::Compressed>,
+                           }];
+
+/// Find a registered day by name.
+pub fn find_day(name: &str) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.name == name)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn all_days_registered() {
+        let names = DAYS.iter().map(|day| day.name).collect::<Vec<_>>();
+        assert_eq!(vec!["day1", "day2", "day3", "day4", "day5", "day6", "day7", "day8", "day9"],
+                   names);
+    }
+
+    #[test]
+    fn find_registered_day() {
+        assert_eq!("Bathroom Security", find_day("day2").unwrap().title);
+        assert!(find_day("day10").is_none());
+    }
+
+    #[test]
+    fn solve_registered_day() {
+        let solution = find_day("day9").unwrap().parse("X(8x2)(3x3)ABCY").unwrap();
+        assert_eq!("18", solution.part1().to_string());
+        assert_eq!("20", solution.part2().to_string());
+    }
+
+    #[test]
+    fn parse_invalid_input() {
+        let err = find_day("day1").unwrap().parse("R1, L2, X3").err().unwrap();
+        assert_eq!(1, err.line());
+        assert_eq!(9, err.column());
+    }
+}
@@ -0,0 +1,188 @@
+//! Advent of Code 2016 runner
+//!
+//! Runs a day's solution against an input file, or against input
+//! piped in on standard input.
+
+extern crate advent;
+
+use std::env;
+use std::fs::File;
+use std::io::{self, Read};
+use std::process;
+
+use advent::*;
+
+const USAGE: &str = "Usage:
+    advent run <day> [--part <1|2>] [--input <file>]
+    advent list
+    advent help
+
+If no `--input` is given, or the file is `-`, the puzzle input is
+read from standard input. If no `--part` is given both parts are run.";
+
+/// A parsed command line
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run {
+        day: String,
+        part: Option<u32>,
+        input: Option<String>,
+    },
+    List,
+    Help,
+}
+
+/// Parse the command line arguments, not including the program name.
+fn parse_args<I>(args: I) -> Result<Command, String>
+    where I: IntoIterator<Item = String>
+{
+    let mut args = args.into_iter();
+    let command = match args.next() {
+        Some(command) => command,
+        None => return Ok(Command::Help),
+    };
+    match &command[..] {
+        "run" => {
+            let mut day = None;
+            let mut part = None;
+            let mut input = None;
+            while let Some(arg) = args.next() {
+                match &arg[..] {
+                    "--part" | "-p" => {
+                        let value = args.next().ok_or("missing value for --part")?;
+                        part = match &value[..] {
+                            "1" => Some(1),
+                            "2" => Some(2),
+                            _ => return Err(format!("invalid part '{}'", value)),
+                        };
+                    }
+                    "--input" | "-i" => {
+                        input = Some(args.next().ok_or("missing value for --input")?);
+                    }
+                    _ if arg.starts_with('-') && arg != "-" => {
+                        return Err(format!("unknown option '{}'", arg))
+                    }
+                    _ if day.is_none() => day = Some(arg),
+                    _ => return Err(format!("unexpected argument '{}'", arg)),
+                }
+            }
+            let day = day.ok_or("no day given")?;
+            Ok(Command::Run {
+                day,
+                part,
+                input,
+            })
+        }
+        "list" => Ok(Command::List),
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        _ => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+/// Read the puzzle input from the given path, or from stdin.
+fn read_input(path: Option<&str>) -> io::Result<String> {
+    let mut input = String::new();
+    match path {
+        Some(path) if path != "-" => File::open(path)?.read_to_string(&mut input)?,
+        _ => io::stdin().read_to_string(&mut input)?,
+    };
+    Ok(input)
+}
+
+/// Solve a given part of the puzzle.
+fn solve(solution: &dyn Solution, part: u32) -> String {
+    let answer = match part {
+        1 => solution.part1(),
+        _ => solution.part2(),
+    };
+    answer.to_string()
+}
+
+fn run(day: &str, part: Option<u32>, input: Option<&str>) -> Result<(), String> {
+    let day = find_day(day).ok_or_else(|| format!("unknown day '{}', see `advent list`", day))?;
+    let input = read_input(input).map_err(|e| format!("could not read input: {}", e))?;
+    let solution = day.parse(input.trim_end()).map_err(|e| format!("invalid input: {}", e))?;
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    for part in parts {
+        let answer = solve(&*solution, part);
+        if answer.contains('\n') {
+            println!("{} part {}:\n{}", day.name, part, answer);
+        } else {
+            println!("{} part {}: {}", day.name, part, answer);
+        }
+    }
+    Ok(())
+}
+
+pub fn main() {
+    let result = match parse_args(env::args().skip(1)) {
+        Ok(Command::Run { day, part, input }) => run(&day, part, input.as_ref().map(|s| &s[..])),
+        Ok(Command::List) => {
+            for day in DAYS {
+                println!("{}: {}", day.name, day.title);
+            }
+            Ok(())
+        }
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Err(e) => Err(format!("{}\n\n{}", e, USAGE)),
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_run_command() {
+        assert_eq!(Ok(Command::Run {
+                       day: "day4".to_string(),
+                       part: Some(2),
+                       input: Some("rooms.txt".to_string()),
+                   }),
+                   parse_args(args("run day4 --part 2 --input rooms.txt")));
+        assert_eq!(Ok(Command::Run {
+                       day: "day1".to_string(),
+                       part: None,
+                       input: None,
+                   }),
+                   parse_args(args("run day1")));
+    }
+
+    #[test]
+    fn parse_invalid_commands() {
+        assert!(parse_args(args("run")).is_err());
+        assert!(parse_args(args("run day1 --part 3")).is_err());
+        assert!(parse_args(args("run day1 --input")).is_err());
+        assert!(parse_args(args("run day1 --frobnicate")).is_err());
+        assert!(parse_args(args("frobnicate")).is_err());
+    }
+
+    #[test]
+    fn parse_other_commands() {
+        assert_eq!(Ok(Command::List), parse_args(args("list")));
+        assert_eq!(Ok(Command::Help), parse_args(args("")));
+        assert_eq!(Ok(Command::Help), parse_args(args("--help")));
+    }
+
+    #[test]
+    fn solve_parts() {
+        let solution = find_day("day1").unwrap().parse("R8, R4, R4, R8").unwrap();
+        assert_eq!("8", solve(&*solution, 1));
+        assert_eq!("4", solve(&*solution, 2));
+    }
+}